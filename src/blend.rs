@@ -0,0 +1,217 @@
+use image::RgbaImage;
+
+/// How the colors of an element are mixed with the colors already drawn beneath it, following the
+/// [W3C Compositing and Blending](https://www.w3.org/TR/compositing-1/#blending) specification,
+/// the same modes found in CSS and design tools.
+/// ## Example
+/// ```
+/// use image_builder::{colors, BlendMode, Image, Rect};
+///
+/// let mut image = Image::new(100, 100, [200, 200, 200, 255]);
+/// image.add_rect(
+///     Rect::new()
+///         .size(50, 50)
+///         .color([128, 128, 128, 255])
+///         .blend_mode(BlendMode::Multiply),
+/// );
+/// let buffer = image.render().unwrap();
+/// assert_eq!(buffer.get_pixel(10, 10).0, [100, 100, 100, 255]);
+/// ```
+///
+/// The results of the modes over opaque colors follow the formulas of the specification, both for
+/// the separable modes, which mix each channel on its own, and for the non-separable ones.
+/// ```
+/// use image_builder::{BlendMode, Image, Rect};
+///
+/// let blend = |mode: BlendMode| {
+///     let mut image = Image::new(10, 10, [200, 100, 50, 255]);
+///     image.add_rect(Rect::new().size(10, 10).color([60, 180, 220, 255]).blend_mode(mode));
+///     image.render().unwrap().get_pixel(5, 5).0
+/// };
+/// assert_eq!(blend(BlendMode::Screen), [213, 209, 227, 255]);
+/// assert_eq!(blend(BlendMode::SoftLight), [177, 125, 96, 255]);
+/// assert_eq!(blend(BlendMode::ColorBurn), [21, 35, 17, 255]);
+/// assert_eq!(blend(BlendMode::Hue), [42, 154, 192, 255]);
+/// assert_eq!(blend(BlendMode::Saturation), [205, 98, 45, 255]);
+/// assert_eq!(blend(BlendMode::Color), [36, 156, 196, 255]);
+/// assert_eq!(blend(BlendMode::Luminosity), [224, 124, 74, 255]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    /// The element is simply drawn over what is beneath it.
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+type Rgb = [f32; 3];
+
+fn multiply(backdrop: f32, source: f32) -> f32 {
+    backdrop * source
+}
+
+fn screen(backdrop: f32, source: f32) -> f32 {
+    backdrop + source - backdrop * source
+}
+
+fn hard_light(backdrop: f32, source: f32) -> f32 {
+    if source <= 0.5 {
+        multiply(backdrop, 2.0 * source)
+    } else {
+        screen(backdrop, 2.0 * source - 1.0)
+    }
+}
+
+fn soft_light(backdrop: f32, source: f32) -> f32 {
+    if source <= 0.5 {
+        backdrop - (1.0 - 2.0 * source) * backdrop * (1.0 - backdrop)
+    } else {
+        let d = if backdrop <= 0.25 {
+            ((16.0 * backdrop - 12.0) * backdrop + 4.0) * backdrop
+        } else {
+            backdrop.sqrt()
+        };
+        backdrop + (2.0 * source - 1.0) * (d - backdrop)
+    }
+}
+
+fn color_dodge(backdrop: f32, source: f32) -> f32 {
+    if backdrop == 0.0 {
+        0.0
+    } else if source >= 1.0 {
+        1.0
+    } else {
+        (backdrop / (1.0 - source)).min(1.0)
+    }
+}
+
+fn color_burn(backdrop: f32, source: f32) -> f32 {
+    if backdrop >= 1.0 {
+        1.0
+    } else if source == 0.0 {
+        0.0
+    } else {
+        1.0 - ((1.0 - backdrop) / source).min(1.0)
+    }
+}
+
+fn lum(c: Rgb) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: Rgb) -> Rgb {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    c.map(|channel| {
+        let mut channel = channel;
+        if n < 0.0 {
+            channel = l + (channel - l) * l / (l - n);
+        }
+        if x > 1.0 {
+            channel = l + (channel - l) * (1.0 - l) / (x - l);
+        }
+        channel
+    })
+}
+
+fn set_lum(c: Rgb, l: f32) -> Rgb {
+    let d = l - lum(c);
+    clip_color(c.map(|channel| channel + d))
+}
+
+fn sat(c: Rgb) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: Rgb, s: f32) -> Rgb {
+    let (min, max) = (c[0].min(c[1]).min(c[2]), c[0].max(c[1]).max(c[2]));
+    if max > min {
+        c.map(|channel| (channel - min) * s / (max - min))
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// Result of the blending function of the mode for a backdrop color and a source color, without
+/// taking their alpha into account.
+fn blend(mode: BlendMode, backdrop: Rgb, source: Rgb) -> Rgb {
+    let separable = |function: fn(f32, f32) -> f32| {
+        [
+            function(backdrop[0], source[0]),
+            function(backdrop[1], source[1]),
+            function(backdrop[2], source[2]),
+        ]
+    };
+    match mode {
+        BlendMode::Normal => source,
+        BlendMode::Multiply => separable(multiply),
+        BlendMode::Screen => separable(screen),
+        BlendMode::Overlay => separable(|b, s| hard_light(s, b)),
+        BlendMode::Darken => separable(f32::min),
+        BlendMode::Lighten => separable(f32::max),
+        BlendMode::ColorDodge => separable(color_dodge),
+        BlendMode::ColorBurn => separable(color_burn),
+        BlendMode::HardLight => separable(hard_light),
+        BlendMode::SoftLight => separable(soft_light),
+        BlendMode::Difference => separable(|b, s| (b - s).abs()),
+        BlendMode::Exclusion => separable(|b, s| b + s - 2.0 * b * s),
+        BlendMode::Hue => set_lum(set_sat(source, sat(backdrop)), lum(backdrop)),
+        BlendMode::Saturation => set_lum(set_sat(backdrop, sat(source)), lum(backdrop)),
+        BlendMode::Color => set_lum(source, lum(backdrop)),
+        BlendMode::Luminosity => set_lum(backdrop, lum(source)),
+    }
+}
+
+/// Draws the layer over the image at the given position, mixing the colors with the blend mode and
+/// compositing the result with the source-over operator.
+pub fn composite(image: &mut RgbaImage, layer: &RgbaImage, x: i64, y: i64, mode: BlendMode) {
+    let left = x.max(0);
+    let top = y.max(0);
+    let right = (x + layer.width() as i64).min(image.width() as i64);
+    let bottom = (y + layer.height() as i64).min(image.height() as i64);
+
+    for image_y in top..bottom {
+        for image_x in left..right {
+            let source = layer
+                .get_pixel((image_x - x) as u32, (image_y - y) as u32)
+                .0;
+            if source[3] == 0 {
+                continue;
+            }
+            let pixel = image.get_pixel_mut(image_x as u32, image_y as u32);
+            let backdrop = pixel.0;
+
+            let source_alpha = source[3] as f32 / 255.0;
+            let backdrop_alpha = backdrop[3] as f32 / 255.0;
+            let source_color = [0, 1, 2].map(|channel| source[channel] as f32 / 255.0);
+            let backdrop_color = [0, 1, 2].map(|channel| backdrop[channel] as f32 / 255.0);
+            let blended = blend(mode, backdrop_color, source_color);
+
+            let alpha = source_alpha + backdrop_alpha * (1.0 - source_alpha);
+            let mut result = [0, 0, 0, (alpha * 255.0).round() as u8];
+            for channel in 0..3 {
+                let mixed = (1.0 - backdrop_alpha) * source_color[channel]
+                    + backdrop_alpha * blended[channel].clamp(0.0, 1.0);
+                let premultiplied = source_alpha * mixed
+                    + backdrop_alpha * backdrop_color[channel] * (1.0 - source_alpha);
+                result[channel] = (premultiplied / alpha * 255.0).round().clamp(0.0, 255.0) as u8;
+            }
+            pixel.0 = result;
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use image::{Rgba, RgbaImage};
+
+use crate::{
+    blend::{self, BlendMode},
+    colors::Color,
+    picture::Filter,
+    raster::Point,
+};
+
+/// Settings shared by every element, applied to the layer where the element is drawn before the
+/// layer is composited over the image.
+#[derive(Clone, Copy, Debug)]
+pub struct Effects {
+    pub rotation: f32,
+    pub pivot: Option<(f32, f32)>,
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
+    pub shadow: Option<(i32, i32, u32, Color)>,
+}
+impl Default for Effects {
+    fn default() -> Self {
+        Effects {
+            rotation: 0.0,
+            pivot: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            shadow: None,
+        }
+    }
+}
+
+/// Largest blur radius of a shadow, in pixels. Beyond it the shadow is too faint to tell apart,
+/// while the cost of the blur keeps growing with the radius.
+pub const MAX_SHADOW_BLUR: u32 = 100;
+
+/// A part of the image, given by its top left and bottom right corners.
+pub type Area = (Point, Point);
+
+/// Area where the pixels of a layer can still land inside the given area of the image once the
+/// effects are applied. A rotation without a pivot turns around the center of the layer, and can
+/// reach anywhere when that center is not known.
+pub fn reach(effects: &Effects, area: Area, center: Option<Point>) -> Area {
+    let ((mut left, mut top), (mut right, mut bottom)) = area;
+    if let Some((offset_x, offset_y, blur_radius, _)) = effects.shadow {
+        let padding = (1.5 * blur_radius.min(MAX_SHADOW_BLUR) as f32).ceil();
+        let (offset_x, offset_y) = (offset_x as f32, offset_y as f32);
+        left = left.min(left - offset_x) - padding;
+        top = top.min(top - offset_y) - padding;
+        right = right.max(right - offset_x) + padding;
+        bottom = bottom.max(bottom - offset_y) + padding;
+    }
+    if effects.rotation % 360.0 != 0.0 {
+        let pivot = match effects.pivot.or(center) {
+            Some(pivot) => pivot,
+            None => {
+                return (
+                    (f32::NEG_INFINITY, f32::NEG_INFINITY),
+                    (f32::INFINITY, f32::INFINITY),
+                )
+            }
+        };
+        // One more pixel for the neighbors read by the bilinear sampling.
+        let radius = [(left, top), (right, top), (right, bottom), (left, bottom)]
+            .map(|corner| (corner.0 - pivot.0).hypot(corner.1 - pivot.1))
+            .into_iter()
+            .fold(0.0, f32::max)
+            + 1.0;
+        return (
+            (pivot.0 - radius, pivot.1 - radius),
+            (pivot.0 + radius, pivot.1 + radius),
+        );
+    }
+    ((left, top), (right, bottom))
+}
+
+/// Applies the effects to the layer of an element placed at the given position, returning the new
+/// layer and its position.
+pub fn apply(layer: RgbaImage, x: i64, y: i64, effects: &Effects) -> (RgbaImage, i64, i64) {
+    let (mut layer, mut x, mut y) = if effects.rotation % 360.0 == 0.0 {
+        (layer, x, y)
+    } else {
+        rotate(&layer, x, y, effects.rotation, effects.pivot)
+    };
+    if let Some(shadow) = effects.shadow {
+        (layer, x, y) = drop_shadow(&layer, x, y, shadow);
+    }
+    if effects.opacity < 1.0 {
+        let opacity = effects.opacity.max(0.0);
+        for pixel in layer.pixels_mut() {
+            pixel.0[3] = (pixel.0[3] as f32 * opacity).round() as u8;
+        }
+    }
+    (layer, x, y)
+}
+
+/// Normalized weights of a gaussian blur with the given standard deviation, covering three times
+/// the deviation on each side.
+pub fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    if sigma <= 0.0 {
+        return vec![1.0];
+    }
+    let radius = (3.0 * sigma).ceil() as i64;
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|offset| (-((offset * offset) as f32) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = kernel.iter().sum();
+    kernel.iter().map(|weight| weight / total).collect()
+}
+
+/// Normalized weights of a box blur, where every pixel up to the radius on each side counts the
+/// same.
+pub fn box_kernel(radius: u32) -> Vec<f32> {
+    let length = 2 * radius as usize + 1;
+    vec![1.0 / length as f32; length]
+}
+
+/// Convolves a plane of values with a symmetric kernel, separately on each axis. The area outside of
+/// the plane counts as zero.
+pub fn convolve(plane: &[f32], width: usize, height: usize, kernel: &[f32]) -> Vec<f32> {
+    let radius = (kernel.len() / 2) as i64;
+    let pass = |source: &[f32], step: usize, length: usize, lines: usize, line_step: usize| {
+        let mut result = vec![0.0; source.len()];
+        for line in 0..lines {
+            for index in 0..length {
+                let mut sum = 0.0;
+                for (offset, weight) in kernel.iter().enumerate() {
+                    let position = index as i64 + offset as i64 - radius;
+                    if (0..length as i64).contains(&position) {
+                        sum += source[line * line_step + position as usize * step] * weight;
+                    }
+                }
+                result[line * line_step + index * step] = sum;
+            }
+        }
+        result
+    };
+    let horizontal = pass(plane, 1, width, height, width);
+    pass(&horizontal, width, height, width, 1)
+}
+
+/// Blurs an image with the kernel, interpolating the colors with premultiplied alpha. Near the
+/// edges, only the pixels inside of the image are averaged, so the edges do not fade out.
+pub fn blur(image: &RgbaImage, kernel: &[f32]) -> RgbaImage {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    if kernel.len() <= 1 || width == 0 || height == 0 {
+        return image.clone();
+    }
+    let mut planes = vec![vec![0.0; width * height]; 4];
+    for (index, pixel) in image.pixels().enumerate() {
+        let alpha = pixel.0[3] as f32;
+        for (plane, value) in planes.iter_mut().zip(pixel.0) {
+            plane[index] = value as f32 * alpha;
+        }
+        planes[3][index] = alpha;
+    }
+    let planes: Vec<Vec<f32>> = planes
+        .iter()
+        .map(|plane| convolve(plane, width, height, kernel))
+        .collect();
+    let weights = convolve(&vec![1.0; width * height], width, height, kernel);
+
+    RgbaImage::from_fn(width as u32, height as u32, |x, y| {
+        let index = y as usize * width + x as usize;
+        let alpha = planes[3][index];
+        if alpha <= 0.0 {
+            return Rgba([0, 0, 0, 0]);
+        }
+        let channel = |c: usize| (planes[c][index] / alpha).round().clamp(0.0, 255.0) as u8;
+        Rgba([
+            channel(0),
+            channel(1),
+            channel(2),
+            (alpha / weights[index]).round().clamp(0.0, 255.0) as u8,
+        ])
+    })
+}
+
+/// Adjusts the colors of every pixel with the filter, using the formulas of the CSS filter functions
+/// on the color channels. The alpha channel is kept.
+pub fn color_filter(image: &mut RgbaImage, filter: Filter) {
+    let matrix = |m: [f32; 9]| {
+        move |c: [f32; 3]| {
+            [
+                m[0] * c[0] + m[1] * c[1] + m[2] * c[2],
+                m[3] * c[0] + m[4] * c[1] + m[5] * c[2],
+                m[6] * c[0] + m[7] * c[1] + m[8] * c[2],
+            ]
+        }
+    };
+    let adjust: Box<dyn Fn([f32; 3]) -> [f32; 3]> = match filter {
+        Filter::Grayscale(amount) => {
+            let s = 1.0 - amount.clamp(0.0, 1.0);
+            Box::new(matrix([
+                0.2126 + 0.7874 * s,
+                0.7152 - 0.7152 * s,
+                0.0722 - 0.0722 * s,
+                0.2126 - 0.2126 * s,
+                0.7152 + 0.2848 * s,
+                0.0722 - 0.0722 * s,
+                0.2126 - 0.2126 * s,
+                0.7152 - 0.7152 * s,
+                0.0722 + 0.9278 * s,
+            ]))
+        }
+        Filter::Sepia(amount) => {
+            let s = 1.0 - amount.clamp(0.0, 1.0);
+            Box::new(matrix([
+                0.393 + 0.607 * s,
+                0.769 - 0.769 * s,
+                0.189 - 0.189 * s,
+                0.349 - 0.349 * s,
+                0.686 + 0.314 * s,
+                0.168 - 0.168 * s,
+                0.272 - 0.272 * s,
+                0.534 - 0.534 * s,
+                0.131 + 0.869 * s,
+            ]))
+        }
+        Filter::Saturation(amount) => {
+            let s = amount.max(0.0);
+            Box::new(matrix([
+                0.213 + 0.787 * s,
+                0.715 - 0.715 * s,
+                0.072 - 0.072 * s,
+                0.213 - 0.213 * s,
+                0.715 + 0.285 * s,
+                0.072 - 0.072 * s,
+                0.213 - 0.213 * s,
+                0.715 - 0.715 * s,
+                0.072 + 0.928 * s,
+            ]))
+        }
+        Filter::HueRotate(degrees) => {
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            Box::new(matrix([
+                0.213 + cos * 0.787 - sin * 0.213,
+                0.715 - cos * 0.715 - sin * 0.715,
+                0.072 - cos * 0.072 + sin * 0.928,
+                0.213 - cos * 0.213 + sin * 0.143,
+                0.715 + cos * 0.285 + sin * 0.140,
+                0.072 - cos * 0.072 - sin * 0.283,
+                0.213 - cos * 0.213 - sin * 0.787,
+                0.715 - cos * 0.715 + sin * 0.715,
+                0.072 + cos * 0.928 + sin * 0.072,
+            ]))
+        }
+        Filter::Brightness(amount) => {
+            let amount = amount.max(0.0);
+            Box::new(move |c: [f32; 3]| c.map(|channel| channel * amount))
+        }
+        Filter::Contrast(amount) => {
+            let amount = amount.max(0.0);
+            Box::new(move |c: [f32; 3]| c.map(|channel| (channel - 0.5) * amount + 0.5))
+        }
+        Filter::Invert(amount) => {
+            let amount = amount.clamp(0.0, 1.0);
+            Box::new(move |c: [f32; 3]| c.map(|channel| amount + channel * (1.0 - 2.0 * amount)))
+        }
+    };
+
+    for pixel in image.pixels_mut() {
+        let color = adjust([0, 1, 2].map(|channel| pixel.0[channel] as f32 / 255.0));
+        for (value, adjusted) in pixel.0.iter_mut().zip(color) {
+            *value = (adjusted * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+/// Draws beneath the layer its silhouette in the color of the shadow, moved by the offset and
+/// blurred by the radius, growing the layer to fit the shadow.
+fn drop_shadow(
+    layer: &RgbaImage,
+    x: i64,
+    y: i64,
+    (offset_x, offset_y, blur_radius, color): (i32, i32, u32, Color),
+) -> (RgbaImage, i64, i64) {
+    // Like in CSS, the blur radius is twice the standard deviation of the blur.
+    let sigma = blur_radius.min(MAX_SHADOW_BLUR) as f32 / 2.0;
+    let padding = (3.0 * sigma).ceil() as i64;
+    let (width, height) = (layer.width() as i64, layer.height() as i64);
+    let shadow_width = (width + 2 * padding) as usize;
+    let shadow_height = (height + 2 * padding) as usize;
+
+    let mut silhouette = vec![0.0; shadow_width * shadow_height];
+    for (px, py, pixel) in layer.enumerate_pixels() {
+        let index = (py as i64 + padding) as usize * shadow_width + (px as i64 + padding) as usize;
+        silhouette[index] = pixel.0[3] as f32 / 255.0;
+    }
+    let silhouette = convolve(
+        &silhouette,
+        shadow_width,
+        shadow_height,
+        &gaussian_kernel(sigma),
+    );
+
+    let (shadow_x, shadow_y) = (x + offset_x as i64 - padding, y + offset_y as i64 - padding);
+    let left = x.min(shadow_x);
+    let top = y.min(shadow_y);
+    let right = (x + width).max(shadow_x + shadow_width as i64);
+    let bottom = (y + height).max(shadow_y + shadow_height as i64);
+    let mut result = RgbaImage::new((right - left) as u32, (bottom - top) as u32);
+    for (index, coverage) in silhouette.iter().enumerate() {
+        let alpha = (color[3] as f32 * coverage).round() as u8;
+        if alpha > 0 {
+            let px = shadow_x - left + (index % shadow_width) as i64;
+            let py = shadow_y - top + (index / shadow_width) as i64;
+            result.put_pixel(
+                px as u32,
+                py as u32,
+                Rgba([color[0], color[1], color[2], alpha]),
+            );
+        }
+    }
+    blend::composite(&mut result, layer, x - left, y - top, BlendMode::Normal);
+    (result, left, top)
+}
+
+/// Rotates the layer clockwise by the given degrees around the pivot, given in the coordinates of
+/// the image, or around the center of the layer when there is no pivot. The layer grows to fit its
+/// rotated corners and the pixels are resampled with bilinear interpolation.
+fn rotate(
+    layer: &RgbaImage,
+    x: i64,
+    y: i64,
+    degrees: f32,
+    pivot: Option<Point>,
+) -> (RgbaImage, i64, i64) {
+    let (width, height) = (layer.width() as f32, layer.height() as f32);
+    let (x, y) = (x as f32, y as f32);
+    let pivot = pivot.unwrap_or((x + width / 2.0, y + height / 2.0));
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let rotated = |point: Point| {
+        let (dx, dy) = (point.0 - pivot.0, point.1 - pivot.1);
+        (pivot.0 + dx * cos - dy * sin, pivot.1 + dx * sin + dy * cos)
+    };
+
+    let corners = [
+        rotated((x, y)),
+        rotated((x + width, y)),
+        rotated((x + width, y + height)),
+        rotated((x, y + height)),
+    ];
+    let (xs, ys) = (corners.map(|c| c.0), corners.map(|c| c.1));
+    let left = xs.into_iter().fold(f32::INFINITY, f32::min).floor();
+    let top = ys.into_iter().fold(f32::INFINITY, f32::min).floor();
+    let right = xs.into_iter().fold(f32::NEG_INFINITY, f32::max).ceil();
+    let bottom = ys.into_iter().fold(f32::NEG_INFINITY, f32::max).ceil();
+
+    let result = RgbaImage::from_fn((right - left) as u32, (bottom - top) as u32, |i, j| {
+        let (dx, dy) = (
+            left + i as f32 + 0.5 - pivot.0,
+            top + j as f32 + 0.5 - pivot.1,
+        );
+        let source = (
+            pivot.0 + dx * cos + dy * sin - x - 0.5,
+            pivot.1 - dx * sin + dy * cos - y - 0.5,
+        );
+        bilinear(layer, source)
+    });
+    (result, left as i64, top as i64)
+}
+
+/// Samples the layer at a point between the centers of its pixels, interpolating the colors with
+/// premultiplied alpha. The area outside of the layer is transparent.
+fn bilinear(layer: &RgbaImage, point: Point) -> Rgba<u8> {
+    let (x0, y0) = (point.0.floor(), point.1.floor());
+    let (fx, fy) = (point.0 - x0, point.1 - y0);
+    let mut sum = [0.0; 4];
+    for (dx, dy, weight) in [
+        (0, 0, (1.0 - fx) * (1.0 - fy)),
+        (1, 0, fx * (1.0 - fy)),
+        (0, 1, (1.0 - fx) * fy),
+        (1, 1, fx * fy),
+    ] {
+        let (px, py) = (x0 as i64 + dx, y0 as i64 + dy);
+        if weight <= 0.0
+            || px < 0
+            || py < 0
+            || px >= layer.width() as i64
+            || py >= layer.height() as i64
+        {
+            continue;
+        }
+        let pixel = layer.get_pixel(px as u32, py as u32).0;
+        let alpha = pixel[3] as f32 * weight;
+        for channel in 0..3 {
+            sum[channel] += pixel[channel] as f32 * alpha;
+        }
+        sum[3] += alpha;
+    }
+
+    if sum[3] <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    Rgba([
+        (sum[0] / sum[3]).round() as u8,
+        (sum[1] / sum[3]).round() as u8,
+        (sum[2] / sum[3]).round() as u8,
+        sum[3].round().min(255.0) as u8,
+    ])
+}
@@ -0,0 +1,262 @@
+use image::Rgba;
+
+use crate::{
+    blend::BlendMode,
+    colors::{self, Color},
+    effects::Effects,
+    fill::Fill,
+};
+
+/// Specifications of an ellipse or circle shape.
+#[derive(Clone)]
+pub struct Ellipse {
+    center: (u32, u32),
+    radii: (u32, u32),
+    fill: Option<Fill>,
+    stroke: Option<(u32, Color)>,
+    effects: Effects,
+}
+impl Ellipse {
+    /// This method instantiates a specifications of an ellipse shape.
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new();
+    /// ```
+    pub fn new() -> Ellipse {
+        Ellipse {
+            center: (5, 5),
+            radii: (5, 5),
+            fill: Some(Fill::Solid(colors::GREEN)),
+            stroke: None,
+            effects: Effects::default(),
+        }
+    }
+
+    /// This method allows you to adjust the position of the center of the ellipse within the image
+    /// being constructed.
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new()
+    ///     .center(100, 100);
+    /// ```
+    pub fn center(&mut self, x: u32, y: u32) -> Self {
+        self.center = (x, y);
+        self.clone()
+    }
+
+    /// Define the same horizontal and vertical radius, turning the ellipse into a circle.
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new()
+    ///     .radius(50);
+    /// ```
+    ///
+    /// Only the part of an ellipse that can show on the image is drawn, so even huge radii render
+    /// quickly.
+    /// ```
+    /// use image_builder::{colors, Ellipse, Image};
+    ///
+    /// let mut image = Image::new(50, 50, colors::WHITE);
+    /// image.add_ellipse(
+    ///     Ellipse::new()
+    ///         .center(25, 25)
+    ///         .radius(3_000_000_000)
+    ///         .color(colors::RED)
+    ///         .stroke(4, colors::BLACK),
+    /// );
+    /// assert_eq!(image.render().unwrap().get_pixel(25, 25).0, colors::RED);
+    /// ```
+    pub fn radius(&mut self, radius: u32) -> Self {
+        self.radii = (radius, radius);
+        self.clone()
+    }
+
+    /// Define the horizontal and vertical radii of the ellipse.
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new()
+    ///     .radii(80, 40);
+    /// ```
+    pub fn radii(&mut self, horizontal: u32, vertical: u32) -> Self {
+        self.radii = (horizontal, vertical);
+        self.clone()
+    }
+
+    /// Define the color of the ellipse.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Ellipse, colors};
+    ///
+    /// Ellipse::new()
+    ///     .color(colors::RED);
+    /// ```
+    pub fn color(&mut self, color: Color) -> Self {
+        self.fill = Some(Fill::Solid(color));
+        self.clone()
+    }
+
+    /// Fill the ellipse with a solid color or a gradient. The positions of a gradient are relative
+    /// to the top left corner of the box around the ellipse. Refer to the [`Fill`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Ellipse, Fill};
+    ///
+    /// Ellipse::new()
+    ///     .radius(50)
+    ///     .fill(Fill::RadialGradient {
+    ///         center: (50.0, 50.0),
+    ///         radius: 50.0,
+    ///         stops: vec![(0.0, colors::YELLOW), (1.0, colors::ORANGE)],
+    ///     });
+    /// ```
+    pub fn fill(&mut self, fill: Fill) -> Self {
+        self.fill = Some(fill);
+        self.clone()
+    }
+
+    /// Remove the fill of the ellipse, so that only its outline is drawn.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Ellipse, colors};
+    ///
+    /// Ellipse::new() // An avatar ring.
+    ///     .radius(50)
+    ///     .no_fill()
+    ///     .stroke(4, colors::GREEN);
+    /// ```
+    pub fn no_fill(&mut self) -> Self {
+        self.fill = None;
+        self.clone()
+    }
+
+    /// Draw a line with the given width in pixels and color along the outline of the ellipse,
+    /// centered on it.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Ellipse, colors};
+    ///
+    /// Ellipse::new()
+    ///     .radius(50)
+    ///     .stroke(4, colors::GREEN);
+    /// ```
+    pub fn stroke(&mut self, width: u32, color: Color) -> Self {
+        self.stroke = Some((width, color));
+        self.clone()
+    }
+
+    /// Rotate the ellipse clockwise by the given degrees around its center, or around the point
+    /// defined by [`Ellipse::pivot`].
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new()
+    ///     .center(100, 100)
+    ///     .radii(80, 30)
+    ///     .rotate(30.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the ellipse is rotated, such as the
+    /// center of an orbit.
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new()
+    ///     .center(150, 100)
+    ///     .radius(10)
+    ///     .rotate(60.0)
+    ///     .pivot(100.0, 100.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the ellipse and its stroke, from `0.0` (invisible) to `1.0` (opaque).
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new()
+    ///     .radius(40)
+    ///     .opacity(0.5);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the colors of the ellipse are mixed with what is beneath it. Refer to the
+    /// [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, BlendMode, Ellipse};
+    ///
+    /// Ellipse::new() // A soft light spot.
+    ///     .radius(80)
+    ///     .color(colors::YELLOW)
+    ///     .blend_mode(BlendMode::Screen);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the ellipse, moved by the offset in pixels and softened by the blur
+    /// radius.
+    /// ## Example
+    /// ```
+    /// use image_builder::Ellipse;
+    ///
+    /// Ellipse::new()
+    ///     .radius(40)
+    ///     .shadow(3, 3, 8, [0, 0, 0, 90]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
+}
+
+impl Default for Ellipse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct EllipseValues<'a> {
+    pub cx: i32,
+    pub cy: i32,
+    pub rx: f32,
+    pub ry: f32,
+    pub fill: Option<&'a Fill>,
+    pub stroke: Option<(f32, Rgba<u8>)>,
+    pub effects: Effects,
+}
+pub fn extract(ellipse: &Ellipse) -> EllipseValues<'_> {
+    EllipseValues {
+        cx: ellipse.center.0 as i32,
+        cy: ellipse.center.1 as i32,
+        rx: ellipse.radii.0 as f32,
+        ry: ellipse.radii.1 as f32,
+        fill: ellipse.fill.as_ref(),
+        stroke: ellipse
+            .stroke
+            .map(|(width, color)| (width as f32, Rgba(color))),
+        effects: ellipse.effects,
+    }
+}
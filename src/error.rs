@@ -0,0 +1,54 @@
+use std::{fmt, io};
+
+use image::ImageError;
+
+/// Errors that can happen while loading fonts, rendering or saving an image.
+#[derive(Debug)]
+pub enum Error {
+    /// The bytes provided to [`crate::Image::add_custom_font`] are not a valid font.
+    FontLoad(String),
+    /// A text refers to a font name that was not loaded using [`crate::Image::add_custom_font`].
+    UnknownFont(String),
+    /// A file could not be read, or the output file could not be created or written.
+    Io(io::Error),
+    /// The data of a picture is not an image in a supported format.
+    Decode(ImageError),
+    /// The rendered image could not be encoded in the requested format.
+    Encode(ImageError),
+    /// An element has dimensions that cannot be drawn, such as a zero sized rect or a crop outside
+    /// the bounds of a picture.
+    InvalidGeometry(String),
+    /// The data given to [`crate::Path::parse`] is not a valid SVG path.
+    InvalidPath(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::FontLoad(name) => write!(f, "Fail to load the font \"{}\"", name),
+            Error::UnknownFont(name) => write!(f, "Unable to load the \"{}\" font, please verify that the name is correct or that it was loaded using the \"add_custom_font\" method.", name),
+            Error::Io(error) => write!(f, "Input/output error: {}", error),
+            Error::Decode(error) => write!(f, "It was not possible to decode the picture: {}", error),
+            Error::Encode(error) => write!(f, "It was not possible to encode the image: {}", error),
+            Error::InvalidGeometry(message) => write!(f, "Invalid geometry: {}", message),
+            Error::InvalidPath(message) => write!(f, "Invalid path data: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::Decode(error) => Some(error),
+            Error::Encode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
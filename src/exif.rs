@@ -0,0 +1,105 @@
+/// Tag of the orientation of the image in the first directory of the EXIF data.
+const ORIENTATION_TAG: u16 = 0x0112;
+
+/// Reads the EXIF orientation, from `1` to `8`, of an encoded JPEG, PNG, WebP or TIFF image.
+/// Images without EXIF data, or with data that cannot be read, have no orientation.
+pub fn orientation(bytes: &[u8]) -> Option<u16> {
+    let tiff = if bytes.starts_with(&[0xFF, 0xD8]) {
+        jpeg_exif(bytes)?
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png_exif(bytes)?
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        webp_exif(bytes)?
+    } else {
+        bytes
+    };
+    tiff_orientation(tiff).filter(|orientation| (1..=8).contains(orientation))
+}
+
+/// Finds the TIFF data inside of the APP1 segment of a JPEG file.
+fn jpeg_exif(bytes: &[u8]) -> Option<&[u8]> {
+    let mut index = 2;
+    while index + 4 <= bytes.len() {
+        if bytes[index] != 0xFF {
+            return None;
+        }
+        let marker = bytes[index + 1];
+        // The image data starts at the start of scan marker, after all the metadata segments.
+        if marker == 0xDA || marker == 0xD9 {
+            return None;
+        }
+        let length = u16::from_be_bytes([bytes[index + 2], bytes[index + 3]]) as usize;
+        let segment = bytes.get(index + 4..index + 2 + length)?;
+        if marker == 0xE1 && segment.starts_with(b"Exif\0\0") {
+            return Some(&segment[6..]);
+        }
+        index += 2 + length;
+    }
+    None
+}
+
+/// Finds the TIFF data inside of the eXIf chunk of a PNG file.
+fn png_exif(bytes: &[u8]) -> Option<&[u8]> {
+    let mut index = 8;
+    while index + 8 <= bytes.len() {
+        let length = u32::from_be_bytes(bytes[index..index + 4].try_into().ok()?) as usize;
+        let kind = &bytes[index + 4..index + 8];
+        let data = bytes.get(index + 8..index + 8 + length)?;
+        match kind {
+            b"eXIf" => return Some(data),
+            b"IDAT" | b"IEND" => return None,
+            _ => index += 12 + length,
+        }
+    }
+    None
+}
+
+/// Finds the TIFF data inside of the EXIF chunk of a WebP file.
+fn webp_exif(bytes: &[u8]) -> Option<&[u8]> {
+    let mut index = 12;
+    while index + 8 <= bytes.len() {
+        let length = u32::from_le_bytes(bytes[index + 4..index + 8].try_into().ok()?) as usize;
+        let data = bytes.get(index + 8..index + 8 + length)?;
+        if &bytes[index..index + 4] == b"EXIF" {
+            return Some(data.strip_prefix(b"Exif\0\0").unwrap_or(data));
+        }
+        // Chunks are padded to an even length.
+        index += 8 + length + length % 2;
+    }
+    None
+}
+
+/// Reads the orientation tag from the first image file directory of TIFF data.
+fn tiff_orientation(tiff: &[u8]) -> Option<u16> {
+    let big_endian = match tiff.get(0..2)? {
+        b"II" => false,
+        b"MM" => true,
+        _ => return None,
+    };
+    let u16_at = |index: usize| {
+        let bytes = [*tiff.get(index)?, *tiff.get(index + 1)?];
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let u32_at = |index: usize| {
+        let bytes: [u8; 4] = tiff.get(index..index + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    if u16_at(2)? != 42 {
+        return None;
+    }
+    let directory = u32_at(4)? as usize;
+    let entries = u16_at(directory)? as usize;
+    (0..entries)
+        .map(|entry| directory + 2 + entry * 12)
+        .find(|&entry| u16_at(entry) == Some(ORIENTATION_TAG))
+        .and_then(|entry| u16_at(entry + 8))
+}
@@ -0,0 +1,179 @@
+use image::{Rgba, RgbaImage};
+
+use crate::{
+    colors::Color,
+    picture::{self, Picture, Repeat},
+};
+
+/// The paint used to fill the background of the image, rects, ellipses, polygons, paths and texts.
+///
+/// Gradients are made of color stops, each one being an offset between `0.0` (the start of the
+/// gradient) and `1.0` (its end) and the color at that offset, in increasing order of offset. The
+/// colors between two stops are interpolated. Any [`Color`] can be used where a `Fill` is
+/// expected, as a solid fill.
+/// ## Example
+/// ```
+/// use image_builder::{colors, Fill, Image, Rect};
+///
+/// let sunset = Fill::LinearGradient {
+///     angle: 90.0,
+///     stops: vec![(0.0, colors::ORANGE), (1.0, colors::PURPLE)],
+/// };
+/// let mut image = Image::new(500, 300, sunset);
+/// image.add_rect(
+///     Rect::new()
+///         .size(100, 100)
+///         .fill(Fill::RadialGradient {
+///             center: (50.0, 50.0),
+///             radius: 50.0,
+///             stops: vec![(0.0, colors::WHITE), (1.0, [255, 255, 255, 0])],
+///         }),
+/// );
+/// ```
+///
+/// Invalid values, such as a NaN radius or stop offset, draw one of the colors of the stops.
+/// ```
+/// use image_builder::{colors, Fill, Image, Rect};
+///
+/// let mut image = Image::new(50, 50, colors::WHITE);
+/// image.add_rect(Rect::new().size(50, 50).fill(Fill::RadialGradient {
+///     center: (f32::NAN, 25.0),
+///     radius: f32::NAN,
+///     stops: vec![(0.0, colors::RED), (f32::NAN, colors::BLUE)],
+/// }));
+/// image.add_rect(Rect::new().size(50, 50).fill(Fill::LinearGradient {
+///     angle: f32::NAN,
+///     stops: vec![(0.0, colors::RED), (1.0, colors::BLUE)],
+/// }));
+/// assert_eq!(image.render().unwrap().get_pixel(25, 25).0, colors::RED);
+/// ```
+#[derive(Clone, Debug)]
+pub enum Fill {
+    Solid(Color),
+    /// A gradient along a line that crosses the element through its center. The angle is given in
+    /// degrees, where `0.0` goes from the bottom to the top and `90.0` from the left to the right.
+    LinearGradient {
+        angle: f32,
+        stops: Vec<(f32, Color)>,
+    },
+    /// A circular gradient. The center and the radius are given in pixels, relative to the top left
+    /// corner of the element.
+    RadialGradient {
+        center: (f32, f32),
+        radius: f32,
+        stops: Vec<(f32, Color)>,
+    },
+    /// A picture repeated from the top left corner of the element, according to the repeat mode
+    /// and the spacing of the picture. The transformations of the picture, such as a resize, are
+    /// applied before it is repeated, while its position and effects are ignored.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Fill, FilterType, Picture, Rect};
+    ///
+    /// let logo = Picture::from_path("logo.png")
+    ///     .unwrap()
+    ///     .resize(40, 25, FilterType::Triangle)
+    ///     .spacing(20, 20);
+    /// Rect::new().size(600, 400).fill(Fill::Pattern(logo));
+    /// ```
+    Pattern(Picture),
+}
+
+impl From<Color> for Fill {
+    fn from(color: Color) -> Self {
+        Fill::Solid(color)
+    }
+}
+
+/// Color of the gradient at the offset, interpolated between the closest stops with premultiplied
+/// alpha so that fading to a transparent color does not darken the gradient.
+fn interpolate(stops: &[(f32, Color)], offset: f32) -> Rgba<u8> {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Rgba([0, 0, 0, 0]),
+    };
+    // A NaN offset, from a NaN angle, center or radius, takes the first color.
+    if offset.is_nan() || offset <= first.0 {
+        return Rgba(first.1);
+    }
+    if offset >= last.0 {
+        return Rgba(last.1);
+    }
+
+    // The end is not found when a stop offset is NaN.
+    let end = match stops.iter().position(|stop| stop.0 >= offset) {
+        Some(end) if end > 0 => end,
+        _ => return Rgba(last.1),
+    };
+    let (start, end) = (stops[end - 1], stops[end]);
+    let t = if end.0 > start.0 {
+        (offset - start.0) / (end.0 - start.0)
+    } else {
+        1.0
+    };
+
+    let alpha = start.1[3] as f32 * (1.0 - t) + end.1[3] as f32 * t;
+    let mut color = [0, 0, 0, alpha.round() as u8];
+    if alpha > 0.0 {
+        for (channel, value) in color.iter_mut().take(3).enumerate() {
+            let premultiplied = start.1[channel] as f32 * start.1[3] as f32 * (1.0 - t)
+                + end.1[channel] as f32 * end.1[3] as f32 * t;
+            *value = (premultiplied / alpha).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    Rgba(color)
+}
+
+/// A fill ready to be drawn, with the picture of a pattern already rendered.
+pub enum Paint<'a> {
+    Fill(&'a Fill),
+    Pattern {
+        pixels: RgbaImage,
+        repeat: Repeat,
+        spacing: (u32, u32),
+    },
+}
+
+/// Color of the paint at a point, relative to the top left corner of an element of the given size.
+pub fn color_at(paint: &Paint, point: (f32, f32), size: (f32, f32)) -> Rgba<u8> {
+    let fill = match paint {
+        Paint::Fill(fill) => fill,
+        Paint::Pattern {
+            pixels,
+            repeat,
+            spacing,
+        } => {
+            let (x, y) = (point.0.floor() as i64, point.1.floor() as i64);
+            return picture::tile_pixel(pixels, x, y, *repeat, *spacing);
+        }
+    };
+    match fill {
+        Fill::Solid(color) => Rgba(*color),
+        Fill::LinearGradient { angle, stops } => {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            let length = (size.0 * sin).abs() + (size.1 * cos).abs();
+            let (dx, dy) = (point.0 - size.0 / 2.0, point.1 - size.1 / 2.0);
+            let offset = if length > 0.0 {
+                (dx * sin - dy * cos) / length + 0.5
+            } else {
+                0.0
+            };
+            interpolate(stops, offset)
+        }
+        Fill::RadialGradient {
+            center,
+            radius,
+            stops,
+        } => {
+            let distance = (point.0 - center.0).hypot(point.1 - center.1);
+            let offset = if *radius > 0.0 {
+                distance / radius
+            } else {
+                1.0
+            };
+            interpolate(stops, offset)
+        }
+        // Patterns are drawn through their rendered picture instead.
+        Fill::Pattern(_) => Rgba([0, 0, 0, 0]),
+    }
+}
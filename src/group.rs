@@ -0,0 +1,190 @@
+use crate::{
+    blend::BlendMode, colors::Color, effects::Effects, ellipse::Ellipse, image::Element,
+    line::Line, path::Path, picture::Picture, polygon::Polygon, polyline::Polyline, rect::Rect,
+    text::Text,
+};
+
+/// A container of elements that are drawn together, as a single layer, before being composited over
+/// the image. The opacity and the rotation of a group apply to all of its elements at once, so
+/// overlapping elements of a faded group do not show through each other.
+/// ## Example
+/// ```
+/// use image_builder::{colors, Group, Image, Rect, Text};
+///
+/// let mut image = Image::new(400, 300, colors::WHITE);
+/// image.add_group(
+///     Group::new()
+///         .add_rect(Rect::new().size(200, 60).position(20, 20).color(colors::PURPLE))
+///         .add_text(Text::new("Faded badge").position(40, 40).color(colors::WHITE))
+///         .opacity(0.5),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Group {
+    elements: Vec<Element>,
+    effects: Effects,
+}
+impl Group {
+    /// This method instantiates an empty group.
+    /// ## Example
+    /// ```
+    /// use image_builder::Group;
+    ///
+    /// Group::new();
+    /// ```
+    pub fn new() -> Group {
+        Group {
+            elements: Vec::new(),
+            effects: Effects::default(),
+        }
+    }
+
+    /// Adds a picture to the group. Refer to the [`Picture`] for more details.
+    pub fn add_picture(&mut self, picture: Picture) -> Self {
+        self.elements.push(Element::Picture(picture));
+        self.clone()
+    }
+
+    /// Adds a text to the group. Refer to the [`Text`] for more details.
+    pub fn add_text(&mut self, text: Text) -> Self {
+        self.elements.push(Element::Text(text));
+        self.clone()
+    }
+
+    /// Adds a rect to the group. Refer to the [`Rect`] for more details.
+    pub fn add_rect(&mut self, rect: Rect) -> Self {
+        self.elements.push(Element::Rect(rect));
+        self.clone()
+    }
+
+    /// Adds an ellipse to the group. Refer to the [`Ellipse`] for more details.
+    pub fn add_ellipse(&mut self, ellipse: Ellipse) -> Self {
+        self.elements.push(Element::Ellipse(ellipse));
+        self.clone()
+    }
+
+    /// Adds a line to the group. Refer to the [`Line`] for more details.
+    pub fn add_line(&mut self, line: Line) -> Self {
+        self.elements.push(Element::Line(line));
+        self.clone()
+    }
+
+    /// Adds a polyline to the group. Refer to the [`Polyline`] for more details.
+    pub fn add_polyline(&mut self, polyline: Polyline) -> Self {
+        self.elements.push(Element::Polyline(polyline));
+        self.clone()
+    }
+
+    /// Adds a polygon to the group. Refer to the [`Polygon`] for more details.
+    pub fn add_polygon(&mut self, polygon: Polygon) -> Self {
+        self.elements.push(Element::Polygon(polygon));
+        self.clone()
+    }
+
+    /// Adds a path to the group. Refer to the [`Path`] for more details.
+    pub fn add_path(&mut self, path: Path) -> Self {
+        self.elements.push(Element::Path(path));
+        self.clone()
+    }
+
+    /// Adds another group inside of this one.
+    pub fn add_group(&mut self, group: Group) -> Self {
+        self.elements.push(Element::Group(group));
+        self.clone()
+    }
+
+    /// Define the opacity of the whole group, from `0.0` (invisible) to `1.0` (opaque).
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Group, Rect};
+    ///
+    /// Group::new()
+    ///     .add_rect(Rect::new().size(100, 100).color(colors::RED))
+    ///     .add_rect(Rect::new().size(100, 100).position(50, 50).color(colors::BLUE))
+    ///     .opacity(0.4);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the colors of the whole group are mixed with what is beneath it, after its
+    /// elements are drawn together. Refer to the [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, BlendMode, Group, Rect};
+    ///
+    /// Group::new()
+    ///     .add_rect(Rect::new().size(100, 100).color(colors::RED))
+    ///     .blend_mode(BlendMode::Screen);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a single shadow beneath all the elements of the group, moved by the offset in pixels and
+    /// softened by the blur radius.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Group, Rect, Text};
+    ///
+    /// Group::new()
+    ///     .add_rect(Rect::new().size(120, 40).color(colors::WHITE))
+    ///     .add_text(Text::new("Badge").position(10, 10))
+    ///     .shadow(0, 3, 8, [0, 0, 0, 80]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
+
+    /// Rotate all the elements of the group clockwise by the given degrees around the center of the
+    /// box around them, or around the point defined by [`Group::pivot`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{Group, Rect, Text};
+    ///
+    /// Group::new()
+    ///     .add_rect(Rect::new().size(120, 40).position(40, 40))
+    ///     .add_text(Text::new("Stamp").position(60, 50))
+    ///     .rotate(-15.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the group is rotated.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Group, Rect};
+    ///
+    /// Group::new()
+    ///     .add_rect(Rect::new().size(120, 40).position(40, 40))
+    ///     .rotate(90.0)
+    ///     .pivot(40.0, 40.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct GroupValues<'a> {
+    pub elements: &'a [Element],
+    pub effects: Effects,
+}
+pub fn extract(group: &Group) -> GroupValues<'_> {
+    GroupValues {
+        elements: &group.elements,
+        effects: group.effects,
+    }
+}
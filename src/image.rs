@@ -1,23 +1,37 @@
-use std::{collections::HashMap, fs::File};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Cursor, Write},
+};
 
-pub use image::imageops::FilterType;
+pub use image::{imageops::FilterType, ImageOutputFormat};
 
 use image::{
-    codecs::png::PngEncoder,
-    imageops::{crop, overlay, resize},
-    ImageBuffer, ImageEncoder, Rgba,
-};
-use imageproc::{
-    drawing::{draw_filled_rect_mut, draw_text_mut, text_size},
-    rect as procRect,
+    imageops::{
+        crop, crop_imm, flip_horizontal, flip_vertical, overlay, resize, rotate180, rotate270,
+        rotate90,
+    },
+    ImageBuffer, Rgba, RgbaImage,
 };
 use rusttype::Font;
 
 use crate::{
-    colors::Color,
-    picture::{self, Picture},
-    rect::{self, Rect},
-    text::{self, Text},
+    blend::{self, BlendMode},
+    effects::{self, Area},
+    ellipse::{self, Ellipse},
+    error::Error,
+    fill::{self, Fill, Paint},
+    group::{self, Group},
+    layout,
+    line::{self, Line},
+    path::{self, Path},
+    picture::{self, Fit, FitValues, Mask, Picture, PictureValues, Transform},
+    polygon::{self, Polygon},
+    polyline::{self, Polyline},
+    raster::{self, Coverage, FillRule, Point},
+    rect::{self, BorderPosition, Rect},
+    stroke::{self, LineCap, LineJoin},
+    text::{self, Overflow, Text, TextMetrics},
 };
 
 #[derive(Clone)]
@@ -25,6 +39,28 @@ pub enum Element {
     Text(Text),
     Rect(Rect),
     Picture(Picture),
+    Ellipse(Ellipse),
+    Line(Line),
+    Polyline(Polyline),
+    Polygon(Polygon),
+    Path(Path),
+    Group(Group),
+}
+
+impl Element {
+    fn effects(&self) -> effects::Effects {
+        match self {
+            Element::Text(element) => text::extract(element).effects,
+            Element::Rect(element) => rect::extract(element).effects,
+            Element::Picture(element) => picture::extract(element).effects,
+            Element::Ellipse(element) => ellipse::extract(element).effects,
+            Element::Line(element) => line::extract(element).effects,
+            Element::Polyline(element) => polyline::extract(element).effects,
+            Element::Polygon(element) => polygon::extract(element).effects,
+            Element::Path(element) => path::extract(element).effects,
+            Element::Group(element) => group::extract(element).effects,
+        }
+    }
 }
 
 /// This is the structure of the image that will be created.
@@ -59,39 +95,54 @@ pub enum Element {
 /// ```
 #[derive(Clone)]
 pub struct Image<'a> {
-    background: Color,
+    background: Fill,
     size: (u32, u32),
     fonts: HashMap<&'a str, Font<'a>>,
     elements: Vec<Element>,
 }
 
 impl<'a> Image<'a> {
-    /// This method creates a new instance of an image, setting the background color, and size in
-    /// pixels, and allocating memory to add fonts and elements to be drawn.
-    /// ## Example
+    /// This method creates a new instance of an image, setting the background color or [`Fill`], and
+    /// size in pixels, and allocating memory to add fonts and elements to be drawn.
+    /// ## Examples
     /// ```
     /// use image_builder::{colors, Image};
     ///
     /// let mut image = Image::new(400, 300, colors::GRAY);
     /// ```
-    pub fn new(width: u32, height: u32, background: Color) -> Image<'a> {
-        let default_font = Vec::from(include_bytes!("Roboto-Regular.ttf") as &[u8]);
-        let default_font = Font::try_from_vec(default_font)
-            .expect("Fail to load the default font \"Roboto-Regular.ttf\"");
+    /// ```
+    /// use image_builder::{colors, Fill, Image};
+    ///
+    /// let mut image = Image::new(
+    ///     400,
+    ///     300,
+    ///     Fill::LinearGradient {
+    ///         angle: 180.0,
+    ///         stops: vec![(0.0, colors::BLUE), (1.0, colors::PURPLE)],
+    ///     },
+    /// );
+    /// ```
+    pub fn new(width: u32, height: u32, background: impl Into<Fill>) -> Image<'a> {
+        let mut fonts = HashMap::new();
+        // The default font is embedded in the library, if it ever fails to load, texts using it will
+        // report an `Error::UnknownFont` when rendered instead of panicking here.
+        if let Some(default_font) = Font::try_from_bytes(include_bytes!("Roboto-Regular.ttf")) {
+            fonts.insert("default", default_font);
+        }
 
         Image {
-            background,
+            background: background.into(),
             size: (width, height),
-            fonts: HashMap::from([("default", default_font)]),
+            fonts,
             elements: Vec::new(),
         }
     }
 
     /// The add_custom_font method requires that a .ttf font file (not provided) be loaded using fs.read,
     /// and internally linked to the provided name in a HashMap. This will allow you to use this font in
-    /// your text by simply passing the font name as a parameter. Trying to use a font that has not been
-    /// loaded cause the application to panic. Additionally, providing an invalid Vec<u8> will also
-    /// result in a panic.
+    /// your text by simply passing the font name as a parameter. Providing an invalid Vec<u8> returns an
+    /// [`Error::FontLoad`], and trying to use a font that has not been loaded makes the rendering return
+    /// an [`Error::UnknownFont`].
     /// ## Example
     /// ```
     /// use image_builder::Image;
@@ -100,11 +151,12 @@ impl<'a> Image<'a> {
     ///
     /// let mut image = Image::new(500, 500, colors::WHITE);
     /// let roboto_bold = fs::read("src/Roboto-Regular.ttf").unwrap();
-    /// image.add_custom_font("Roboto Regular", roboto_bold);
+    /// image.add_custom_font("Roboto Regular", roboto_bold).unwrap();
     /// ```
-    pub fn add_custom_font(&mut self, name: &'a str, font: Vec<u8>) {
-        let font = Font::try_from_vec(font).expect(&format!("Fail to load the font \"{}\"", name));
+    pub fn add_custom_font(&mut self, name: &'a str, font: Vec<u8>) -> Result<(), Error> {
+        let font = Font::try_from_vec(font).ok_or_else(|| Error::FontLoad(String::from(name)))?;
         self.fonts.insert(name, font);
+        Ok(())
     }
 
     /// With this method, it is possible to add an image on top of the image being built, taking into account
@@ -120,11 +172,53 @@ impl<'a> Image<'a> {
     }
 
     /// This method can be used before `add_text` to reqeust the expected width and height of a
-    /// text element.
-    pub fn text_size(&mut self, text: &Text) -> (i32, i32) {
-        let t = text::extract(&text);
-        let font = self.fonts.get(t.font_name).expect(&format!("Unable to load the \"{}\" font, please verify that the name is correct or that it was loaded using the \"add_custom_font\" method.", t.font_name));
-        text_size(t.scale, font, &t.content)
+    /// text element. For texts with multiple lines, the size of the whole block is returned.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Image, Text};
+    ///
+    /// let image = Image::new(500, 500, colors::WHITE);
+    /// let (_, single_line) = image.text_size(&Text::new("Image Builder")).unwrap();
+    /// let (_, two_lines) = image.text_size(&Text::new("Image\nBuilder")).unwrap();
+    /// assert!(two_lines > single_line);
+    /// ```
+    pub fn text_size(&self, text: &Text) -> Result<(i32, i32), Error> {
+        let t = text::extract(text);
+        let font = self.font(t.font_name)?;
+        let block = layout::layout(font, &t);
+        Ok(layout::visible_size(&t, &block))
+    }
+
+    /// Lays out a text the same way it is drawn and returns its measurements, including the size
+    /// chosen by [`Text::fit_within`], which helps to place other elements around it.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Image, Text};
+    ///
+    /// let image = Image::new(500, 500, colors::WHITE);
+    /// let title = Text::new("Image Builder").fit_within(200, 100).max_size(90);
+    /// let metrics = image.measure_text(&title).unwrap();
+    /// assert!(metrics.size < 90);
+    /// assert!(metrics.width <= 200);
+    /// assert_eq!(metrics.lines, 1);
+    /// ```
+    pub fn measure_text(&self, text: &Text) -> Result<TextMetrics, Error> {
+        let t = text::extract(text);
+        let font = self.font(t.font_name)?;
+        let block = layout::layout(font, &t);
+        let (width, height) = layout::visible_size(&t, &block);
+        Ok(TextMetrics {
+            size: block.scale.y.round() as u32,
+            width,
+            height,
+            lines: block.lines.len(),
+        })
+    }
+
+    fn font(&self, name: &str) -> Result<&Font<'a>, Error> {
+        self.fonts
+            .get(name)
+            .ok_or_else(|| Error::UnknownFont(String::from(name)))
     }
 
     /// This method allows for adding rectangular shapes to the image being built. Refer to the [`Rect`] for more details.
@@ -132,64 +226,625 @@ impl<'a> Image<'a> {
         self.elements.push(Element::Rect(rect));
     }
 
-    /// The save method is responsible for the entire rendering process of the library. It creates the image buffer and
-    /// renders the list of elements added in the order they were inserted by the user. Then, it creates the image file,
-    /// adds the generated buffer, and encodes the content to save it to the disk.
-    pub fn save(&mut self, file_name: &str) {
-        let mut image = ImageBuffer::from_pixel(self.size.0, self.size.1, Rgba(self.background));
+    /// This method allows for adding ellipses and circles to the image being built. Refer to the [`Ellipse`] for more details.
+    pub fn add_ellipse(&mut self, ellipse: Ellipse) {
+        self.elements.push(Element::Ellipse(ellipse));
+    }
+
+    /// This method allows for adding straight lines to the image being built. Refer to the [`Line`] for more details.
+    pub fn add_line(&mut self, line: Line) {
+        self.elements.push(Element::Line(line));
+    }
+
+    /// This method allows for adding lines made of connected segments to the image being built. Refer to the
+    /// [`Polyline`] for more details.
+    pub fn add_polyline(&mut self, polyline: Polyline) {
+        self.elements.push(Element::Polyline(polyline));
+    }
+
+    /// This method allows for adding polygons to the image being built. Refer to the [`Polygon`] for more details.
+    pub fn add_polygon(&mut self, polygon: Polygon) {
+        self.elements.push(Element::Polygon(polygon));
+    }
 
+    /// This method allows for adding vector paths, such as icons, to the image being built. Refer to the [`Path`] for
+    /// more details.
+    pub fn add_path(&mut self, path: Path) {
+        self.elements.push(Element::Path(path));
+    }
+
+    /// This method allows for adding a group of elements that are drawn together, sharing their opacity and
+    /// rotation. Refer to the [`Group`] for more details.
+    pub fn add_group(&mut self, group: Group) {
+        self.elements.push(Element::Group(group));
+    }
+
+    /// The render method is responsible for the entire rendering process of the library. It creates the image
+    /// buffer and renders the list of elements added in the order they were inserted by the user. The resulting
+    /// buffer can be further processed with the [image](https://crates.io/crates/image) crate.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Image, Rect};
+    ///
+    /// let mut image = Image::new(100, 100, colors::WHITE);
+    /// image.add_rect(Rect::new().size(50, 50).color(colors::RED));
+    ///
+    /// let buffer = image.render().unwrap();
+    /// assert_eq!(buffer.get_pixel(10, 10).0, colors::RED);
+    /// assert_eq!(buffer.get_pixel(90, 90).0, colors::WHITE);
+    /// ```
+    pub fn render(&self) -> Result<RgbaImage, Error> {
+        if self.size.0 == 0 || self.size.1 == 0 {
+            return Err(Error::InvalidGeometry(format!(
+                "the image size must be positive, got {}x{}",
+                self.size.0, self.size.1
+            )));
+        }
+        let size = (self.size.0 as f32, self.size.1 as f32);
+        let background = paint(&self.background)?;
+        let mut image = ImageBuffer::from_fn(self.size.0, self.size.1, |x, y| {
+            fill::color_at(&background, (x as f32 + 0.5, y as f32 + 0.5), size)
+        });
+
+        let area = ((0.0, 0.0), size);
         for element in self.elements.iter() {
-            match element {
-                Element::Picture(element) => {
-                    let p = picture::extract(element);
-                    let mut pic = p.img.to_rgba8();
+            if let Some((layer, x, y, mode)) = self.layer(element, &image, area)? {
+                blend::composite(&mut image, &layer, x, y, mode);
+            }
+        }
+
+        Ok(image)
+    }
 
-                    if let Some(values) = p.resize {
-                        pic = resize(&mut pic, values.nwidth, values.nheight, values.filter)
+    /// Draws an element into its layer and applies the effects of the element to it, returning the
+    /// layer, its position and the blend mode used to composite it. The backdrop is what was already
+    /// drawn beneath the element, and the area is the part of the image where the layer can show.
+    fn layer(
+        &self,
+        element: &Element,
+        backdrop: &RgbaImage,
+        area: Area,
+    ) -> Result<Option<(RgbaImage, i64, i64, BlendMode)>, Error> {
+        let effects = element.effects();
+        Ok(self.draw(element, backdrop, area)?.map(|(layer, x, y)| {
+            let (layer, x, y) = effects::apply(layer, x, y, &effects);
+            (layer, x, y, effects.blend_mode)
+        }))
+    }
+
+    /// Draws an element into a layer that covers only the area of the element, returning the
+    /// layer and its position in the image, or nothing when the element is empty. Shapes leave out
+    /// what cannot reach the area once their effects are applied.
+    fn draw(
+        &self,
+        element: &Element,
+        backdrop: &RgbaImage,
+        area: Area,
+    ) -> Result<Option<(RgbaImage, i64, i64)>, Error> {
+        let effects = element.effects();
+        let reach = |center| effects::reach(&effects, area, Some(center));
+        let layer = match element {
+            Element::Picture(element) => {
+                let p = picture::extract(element);
+                Some((render_picture(&p)?, p.x, p.y))
+            }
+            Element::Text(element) => {
+                let t = text::extract(element);
+                let font = self.font(t.font_name)?;
+                let block = layout::layout(font, &t);
+                let left = t.x + block.lines.iter().map(|line| line.x).min().unwrap_or(0);
+                let top = t.y + block.lines.first().map_or(0, |line| line.y);
+
+                // Glyphs may reach a little beyond the measured block, such as italic overhangs.
+                let outline_width = t.stroke.map_or(0.0, |(width, _)| width);
+                let padding = (block.scale.y / 4.0 + outline_width).ceil() as i32 + 1;
+                let (origin_x, origin_y) = (left - padding, top - padding);
+                let layer_width = (block.width + 2 * padding).max(1) as u32;
+                let layer_height = (block.height + 2 * padding).max(1) as u32;
+                let mut text_image =
+                    ImageBuffer::from_pixel(layer_width, layer_height, Rgba([0, 0, 0, 0]));
+                let mut mask = Coverage::new(layer_width, layer_height);
+                let mut outline_pieces = Vec::new();
+                for line in block.lines.iter() {
+                    let (line_x, line_y) = (t.x + line.x - origin_x, t.y + line.y - origin_y);
+                    raster::glyphs(&mut mask, font, block.scale, &line.content, line_x, line_y);
+                    if let Some((width, color)) = t.stroke {
+                        // The outline is centered on the contours, so it is twice as wide to show
+                        // the given width outside of the glyphs.
+                        let outline = stroke::StrokeValues {
+                            width: 2.0 * width,
+                            color,
+                            cap: LineCap::Round,
+                            join: LineJoin::Round,
+                            dash: &[],
+                        };
+                        for contour in
+                            raster::glyph_contours(font, block.scale, &line.content, line_x, line_y)
+                        {
+                            outline_pieces.extend(stroke::outline(&contour, true, &outline));
+                        }
                     }
-                    if let Some(values) = p.crop {
-                        pic = crop(&mut pic, values.x, values.y, values.width, values.height)
-                            .to_image();
+                }
+                let mut outline_mask = t.stroke.map(|_| {
+                    raster::fill(
+                        &outline_pieces,
+                        FillRule::NonZero,
+                        layer_width,
+                        layer_height,
+                    )
+                });
+                if *t.overflow == Overflow::Clip {
+                    let box_top = t.y + block.top;
+                    let right = t.max_width.map(|width| t.x + width as i32);
+                    let bottom = t.max_height.map(|height| box_top + height as i32);
+                    for y in 0..mask.height {
+                        for x in 0..mask.width {
+                            let (mx, my) = (origin_x + x as i32, origin_y + y as i32);
+                            let outside_x = right.is_some_and(|right| mx < t.x || mx >= right);
+                            let outside_y =
+                                bottom.is_some_and(|bottom| my < box_top || my >= bottom);
+                            if outside_x || outside_y {
+                                mask.set(x, y, 0.0);
+                                if let Some(outline_mask) = outline_mask.as_mut() {
+                                    outline_mask.set(x, y, 0.0);
+                                }
+                            }
+                        }
                     }
+                }
+                if let (Some(outline_mask), Some((_, color))) = (outline_mask, t.stroke) {
+                    raster::paint(&mut text_image, &outline_mask, |_, _| color);
+                }
 
-                    overlay(&mut image, &pic, p.x, p.y);
+                let size = (block.width as f32, block.height as f32);
+                let text_paint = paint(t.fill)?;
+                raster::paint(&mut text_image, &mask, |x, y| {
+                    let point = (
+                        (x as i32 + origin_x - left) as f32 + 0.5,
+                        (y as i32 + origin_y - top) as f32 + 0.5,
+                    );
+                    fill::color_at(&text_paint, point, size)
+                });
+                Some((text_image, origin_x as i64, origin_y as i64))
+            }
+            Element::Rect(element) => {
+                let r = rect::extract(element);
+                if r.width == 0 || r.height == 0 {
+                    return Err(Error::InvalidGeometry(format!(
+                        "the rect size must be positive, got {}x{}",
+                        r.width, r.height
+                    )));
                 }
-                Element::Text(element) => {
-                    let t = text::extract(&element);
-                    let font = self.fonts.get(t.font_name).expect(&format!("Unable to load the \"{}\" font, please verify that the name is correct or that it was loaded using the \"add_custom_font\" method.", t.font_name));
-                    let mut text_image =
-                        ImageBuffer::from_pixel(self.size.0, self.size.1, Rgba([0, 0, 0, 0]));
-                    draw_text_mut(&mut text_image, t.color, 0, 0, t.scale, font, t.content);
-                    overlay(&mut image, &text_image, t.x as i64, t.y as i64);
+                let outset = match (r.border, r.border_position) {
+                    (Some((width, _)), BorderPosition::Outer) => width,
+                    (Some((width, _)), BorderPosition::Center) => width / 2.0,
+                    _ => 0.0,
+                };
+                let padding = outset.ceil();
+                let layer_width = r.width + 2 * padding as u32;
+                let layer_height = r.height + 2 * padding as u32;
+                let (width, height) = (r.width as f32, r.height as f32);
+                let mut rect_image =
+                    ImageBuffer::from_pixel(layer_width, layer_height, Rgba([0, 0, 0, 0]));
+
+                let body = raster::rounded_rect(padding, padding, width, height, r.corner_radii);
+                let mask = raster::fill(&[body], FillRule::NonZero, layer_width, layer_height);
+                if let Some(sigma) = r.backdrop_blur {
+                    let (layer_x, layer_y) =
+                        (r.x as i64 - padding as i64, r.y as i64 - padding as i64);
+                    // The blur reads the pixels around the rect as well, so that its edges are not
+                    // sharper than its middle.
+                    let margin = (3.0 * sigma).ceil().max(0.0) as i64;
+                    let left = (layer_x - margin).max(0);
+                    let top = (layer_y - margin).max(0);
+                    let right =
+                        (layer_x + layer_width as i64 + margin).min(backdrop.width() as i64);
+                    let bottom =
+                        (layer_y + layer_height as i64 + margin).min(backdrop.height() as i64);
+                    if right > left && bottom > top {
+                        let region = crop_imm(
+                            backdrop,
+                            left as u32,
+                            top as u32,
+                            (right - left) as u32,
+                            (bottom - top) as u32,
+                        )
+                        .to_image();
+                        let blurred = effects::blur(&region, &effects::gaussian_kernel(sigma));
+                        raster::paint(&mut rect_image, &mask, |x, y| {
+                            let (bx, by) = (layer_x + x as i64 - left, layer_y + y as i64 - top);
+                            if (0..blurred.width() as i64).contains(&bx)
+                                && (0..blurred.height() as i64).contains(&by)
+                            {
+                                *blurred.get_pixel(bx as u32, by as u32)
+                            } else {
+                                Rgba([0, 0, 0, 0])
+                            }
+                        });
+                    }
                 }
-                Element::Rect(element) => {
-                    let r = rect::extract(element);
-                    let mut rect_image =
-                        ImageBuffer::from_pixel(r.width, r.height, Rgba([0, 0, 0, 0]));
-
-                    draw_filled_rect_mut(
-                        &mut rect_image,
-                        procRect::Rect::at(0, 0).of_size(r.width, r.height),
-                        r.color,
+                let size = (width, height);
+                let rect_paint = paint(r.fill)?;
+                raster::paint(&mut rect_image, &mask, |x, y| {
+                    let point = (x as f32 - padding + 0.5, y as f32 - padding + 0.5);
+                    fill::color_at(&rect_paint, point, size)
+                });
+
+                if let Some((border_width, border_color)) = r.border {
+                    let inset = border_width - outset;
+                    let outer = raster::rounded_rect(
+                        padding - outset,
+                        padding - outset,
+                        width + 2.0 * outset,
+                        height + 2.0 * outset,
+                        r.corner_radii.map(
+                            |radius| {
+                                if radius > 0.0 {
+                                    radius + outset
+                                } else {
+                                    0.0
+                                }
+                            },
+                        ),
                     );
+                    let mut contours = vec![outer];
+                    if width > 2.0 * inset && height > 2.0 * inset {
+                        contours.push(raster::rounded_rect(
+                            padding + inset,
+                            padding + inset,
+                            width - 2.0 * inset,
+                            height - 2.0 * inset,
+                            r.corner_radii.map(|radius| (radius - inset).max(0.0)),
+                        ));
+                    }
+                    let mask =
+                        raster::fill(&contours, FillRule::EvenOdd, layer_width, layer_height);
+                    raster::paint(&mut rect_image, &mask, |_, _| border_color);
+                }
 
-                    overlay(&mut image, &rect_image, r.x as i64, r.y as i64);
+                Some((
+                    rect_image,
+                    r.x as i64 - padding as i64,
+                    r.y as i64 - padding as i64,
+                ))
+            }
+            Element::Ellipse(element) => {
+                let e = ellipse::extract(element);
+                let center = (e.cx as f32, e.cy as f32);
+                let body = vec![raster::ellipse(center, (e.rx, e.ry))];
+                let fill_paint = e.fill.map(paint).transpose()?;
+                let fill = fill_paint
+                    .as_ref()
+                    .map(|fill| (body.as_slice(), FillRule::NonZero, fill));
+
+                // The stroke is the ring between two ellipses, with the inner one reversed so that
+                // the ring is filled by the non-zero rule.
+                let ring = e.stroke.map(|(stroke_width, _)| {
+                    let half = stroke_width / 2.0;
+                    let mut ring = vec![raster::ellipse(center, (e.rx + half, e.ry + half))];
+                    if e.rx > half && e.ry > half {
+                        let mut inner = raster::ellipse(center, (e.rx - half, e.ry - half));
+                        inner.reverse();
+                        ring.push(inner);
+                    }
+                    ring
+                });
+                let stroke = ring
+                    .as_ref()
+                    .zip(e.stroke)
+                    .map(|(ring, (_, color))| (ring.as_slice(), color));
+                raster::shape(fill, stroke, reach)
+            }
+            Element::Line(_) | Element::Polyline(_) => {
+                let l = match element {
+                    Element::Line(element) => line::extract(element),
+                    Element::Polyline(element) => polyline::extract(element),
+                    _ => unreachable!(),
+                };
+                let pieces = stroke::outline(&l.points, false, &l.stroke);
+                raster::shape(None, Some((&pieces, l.stroke.color)), reach)
+            }
+            Element::Polygon(element) => {
+                let p = polygon::extract(element);
+                let contours = vec![p.points.to_vec()];
+                let pieces = p
+                    .stroke
+                    .as_ref()
+                    .map(|s| stroke::outline(p.points, true, s));
+                let fill_paint = p.fill.map(paint).transpose()?;
+                let fill = fill_paint
+                    .as_ref()
+                    .map(|fill| (contours.as_slice(), FillRule::NonZero, fill));
+                let stroke = pieces
+                    .as_ref()
+                    .zip(p.stroke.as_ref())
+                    .map(|(pieces, s)| (pieces.as_slice(), s.color));
+                raster::shape(fill, stroke, reach)
+            }
+            Element::Path(element) => {
+                let p = path::extract(element);
+                let contours: Vec<Vec<Point>> = p
+                    .contours
+                    .iter()
+                    .map(|(points, _)| points.clone())
+                    .collect();
+                let pieces = p.stroke.as_ref().map(|s| {
+                    p.contours
+                        .iter()
+                        .flat_map(|(points, closed)| stroke::outline(points, *closed, s))
+                        .collect::<Vec<_>>()
+                });
+                let fill_paint = p.fill.map(paint).transpose()?;
+                let fill = fill_paint
+                    .as_ref()
+                    .map(|fill| (contours.as_slice(), p.fill_rule, fill));
+                let stroke = pieces
+                    .as_ref()
+                    .zip(p.stroke.as_ref())
+                    .map(|(pieces, s)| (pieces.as_slice(), s.color));
+                raster::shape(fill, stroke, reach)
+            }
+            Element::Group(element) => {
+                let g = group::extract(element);
+                let area = effects::reach(&g.effects, area, None);
+                let mut layers = Vec::new();
+                for element in g.elements {
+                    if let Some(layer) = self.layer(element, backdrop, area)? {
+                        layers.push(layer);
+                    }
+                }
+                let left = layers.iter().map(|(_, x, _, _)| *x).min();
+                let top = layers.iter().map(|(_, _, y, _)| *y).min();
+                let right = layers.iter().map(|(l, x, _, _)| x + l.width() as i64).max();
+                let bottom = layers
+                    .iter()
+                    .map(|(l, _, y, _)| y + l.height() as i64)
+                    .max();
+                match (left, top, right, bottom) {
+                    (Some(left), Some(top), Some(right), Some(bottom)) => {
+                        let mut group_image =
+                            RgbaImage::new((right - left) as u32, (bottom - top) as u32);
+                        for (layer, x, y, mode) in layers.iter() {
+                            blend::composite(&mut group_image, layer, x - left, y - top, *mode);
+                        }
+                        Some((group_image, left, top))
+                    }
+                    _ => None,
                 }
             }
+        };
+        Ok(layer)
+    }
+
+    /// Renders the image and encodes it in the given format into any writer, such as a file, an
+    /// in-memory buffer or a network stream. The image is encoded in memory first, since some
+    /// formats need to seek back in their output, and then written out in one go.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Image, ImageOutputFormat};
+    ///
+    /// let image = Image::new(100, 100, colors::WHITE);
+    /// let mut bytes: Vec<u8> = Vec::new();
+    /// image.encode_to(&mut bytes, ImageOutputFormat::Jpeg(90)).unwrap();
+    /// assert_eq!(&bytes[..2], &[0xFF, 0xD8]);
+    ///
+    /// // Writers that cannot seek, such as standard output or a socket, work too.
+    /// image.encode_to(&mut std::io::sink(), ImageOutputFormat::Png).unwrap();
+    /// ```
+    pub fn encode_to<W, F>(&self, writer: &mut W, format: F) -> Result<(), Error>
+    where
+        W: Write,
+        F: Into<ImageOutputFormat>,
+    {
+        let mut buffer = Cursor::new(Vec::new());
+        self.render()?
+            .write_to(&mut buffer, format)
+            .map_err(Error::Encode)?;
+        writer.write_all(&buffer.into_inner())?;
+        Ok(())
+    }
+
+    /// Renders the image and returns the content of the encoded PNG file.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Image};
+    ///
+    /// let image = Image::new(100, 100, colors::WHITE);
+    /// let png = image.to_png_bytes().unwrap();
+    /// assert_eq!(&png[1..4], b"PNG");
+    /// ```
+    pub fn to_png_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = Cursor::new(Vec::new());
+        self.render()?
+            .write_to(&mut buffer, ImageOutputFormat::Png)
+            .map_err(Error::Encode)?;
+        Ok(buffer.into_inner())
+    }
+
+    /// The save method renders the image (see [`Image::render`]), creates the image file and encodes the
+    /// content as PNG to save it to the disk. An [`Error::Io`] is returned if the file cannot be created,
+    /// for example because the file path does not exist.
+    pub fn save(&self, file_name: &str) -> Result<(), Error> {
+        let mut file = File::create(file_name)?;
+        self.encode_to(&mut file, ImageOutputFormat::Png)
+    }
+}
+
+/// Decodes a picture and applies its orientation, its transformations and its mask.
+fn render_picture(p: &PictureValues) -> Result<RgbaImage, Error> {
+    let mut pic = picture::decode(p.img)?;
+
+    for step in p.orientation.iter().chain(p.transforms) {
+        pic = transform_picture(pic, step, p)?;
+    }
+    if let Some(mask) = p.mask {
+        apply_mask(&mut pic, mask);
+    }
+
+    Ok(pic)
+}
+
+/// Prepares a fill to be drawn, rendering the picture of a pattern.
+fn paint(fill: &Fill) -> Result<Paint<'_>, Error> {
+    match fill {
+        Fill::Pattern(pattern) => {
+            let p = picture::extract(pattern);
+            Ok(Paint::Pattern {
+                pixels: render_picture(&p)?,
+                repeat: p.repeat,
+                spacing: p.spacing,
+            })
         }
+        _ => Ok(Paint::Fill(fill)),
+    }
+}
 
-        let file = File::create(file_name).expect(&format!(
-            "It was not possible to create the file \"{}\" because the file path does not exist.",
-            file_name
-        ));
-        let encoder = PngEncoder::new(file);
-        encoder
-            .write_image(
-                &image,
-                image.width(),
-                image.height(),
-                image::ColorType::Rgba8,
+/// Applies a single transformation step to the picture.
+fn transform_picture(
+    mut picture: RgbaImage,
+    step: &Transform,
+    p: &PictureValues,
+) -> Result<RgbaImage, Error> {
+    match step {
+        Transform::Crop(values) => {
+            if values.width == 0
+                || values.height == 0
+                || values.x > picture.width()
+                || values.width > picture.width() - values.x
+                || values.y > picture.height()
+                || values.height > picture.height() - values.y
+            {
+                return Err(Error::InvalidGeometry(format!(
+                    "the crop of {}x{} at ({}, {}) is outside of the {}x{} picture",
+                    values.width,
+                    values.height,
+                    values.x,
+                    values.y,
+                    picture.width(),
+                    picture.height()
+                )));
+            }
+            Ok(crop(
+                &mut picture,
+                values.x,
+                values.y,
+                values.width,
+                values.height,
             )
-            .unwrap();
+            .to_image())
+        }
+        Transform::Resize(values) => {
+            if values.nwidth == 0 || values.nheight == 0 {
+                return Err(Error::InvalidGeometry(format!(
+                    "a picture cannot be resized to {}x{}",
+                    values.nwidth, values.nheight
+                )));
+            }
+            Ok(resize(
+                &picture,
+                values.nwidth,
+                values.nheight,
+                values.filter,
+            ))
+        }
+        Transform::Fit(values) => {
+            if values.width == 0 || values.height == 0 {
+                return Err(Error::InvalidGeometry(format!(
+                    "a picture cannot be fitted into {}x{}",
+                    values.width, values.height
+                )));
+            }
+            Ok(fit_picture(&picture, values, p.focal_point))
+        }
+        Transform::Blur(sigma) => Ok(effects::blur(&picture, &effects::gaussian_kernel(*sigma))),
+        Transform::BoxBlur(radius) => Ok(effects::blur(&picture, &effects::box_kernel(*radius))),
+        Transform::Filter(filter) => {
+            effects::color_filter(&mut picture, *filter);
+            Ok(picture)
+        }
+        Transform::FlipHorizontal => Ok(flip_horizontal(&picture)),
+        Transform::FlipVertical => Ok(flip_vertical(&picture)),
+        Transform::Rotate90 => Ok(rotate90(&picture)),
+        Transform::Rotate180 => Ok(rotate180(&picture)),
+        Transform::Rotate270 => Ok(rotate270(&picture)),
+        Transform::Tile(width, height) => {
+            if *width == 0 || *height == 0 {
+                return Err(Error::InvalidGeometry(format!(
+                    "a picture cannot be tiled over {}x{}",
+                    width, height
+                )));
+            }
+            Ok(ImageBuffer::from_fn(*width, *height, |x, y| {
+                picture::tile_pixel(&picture, x as i64, y as i64, p.repeat, p.spacing)
+            }))
+        }
+    }
+}
+
+/// Multiplies the alpha channel of the picture by the coverage of the mask.
+fn apply_mask(picture: &mut RgbaImage, mask: &Mask) {
+    let (width, height) = picture.dimensions();
+    let shape = match mask {
+        Mask::Circle => raster::ellipse(
+            (width as f32 / 2.0, height as f32 / 2.0),
+            (width as f32 / 2.0, height as f32 / 2.0),
+        ),
+        Mask::RoundedRect(radius) => {
+            raster::rounded_rect(0.0, 0.0, width as f32, height as f32, [*radius as f32; 4])
+        }
+        Mask::Alpha(alpha) => {
+            let mut alpha = alpha.to_rgba8();
+            if alpha.dimensions() != (width, height) {
+                alpha = resize(&alpha, width, height, FilterType::Triangle);
+            }
+            for (pixel, mask_pixel) in picture.pixels_mut().zip(alpha.pixels()) {
+                pixel.0[3] = (pixel.0[3] as u16 * mask_pixel.0[3] as u16 / 255) as u8;
+            }
+            return;
+        }
+    };
+    let coverage = raster::fill(&[shape], FillRule::NonZero, width, height);
+    for (x, y, pixel) in picture.enumerate_pixels_mut() {
+        pixel.0[3] = (pixel.0[3] as f32 * coverage.get(x, y)).round() as u8;
+    }
+}
+
+/// Scales the picture into a slot according to the fit mode, placing it or cutting it off around
+/// the focal point.
+fn fit_picture(picture: &RgbaImage, values: &FitValues, focal_point: (f32, f32)) -> RgbaImage {
+    let (width, height) = (picture.width() as f32, picture.height() as f32);
+    let (slot_width, slot_height) = (values.width as f32, values.height as f32);
+    if values.mode == Fit::Fill {
+        return resize(picture, values.width, values.height, FilterType::CatmullRom);
+    }
+
+    let scale = match values.mode {
+        Fit::Cover => (slot_width / width).max(slot_height / height),
+        Fit::ScaleDown => (slot_width / width).min(slot_height / height).min(1.0),
+        _ => (slot_width / width).min(slot_height / height),
+    };
+    let scaled_width = ((width * scale).round() as u32).max(1);
+    let scaled_height = ((height * scale).round() as u32).max(1);
+    let mut scaled = if (scaled_width, scaled_height) == picture.dimensions() {
+        picture.clone()
+    } else {
+        resize(picture, scaled_width, scaled_height, FilterType::CatmullRom)
+    };
+
+    let (focal_x, focal_y) = focal_point;
+    let offset_x = ((slot_width - scaled_width as f32) * focal_x.clamp(0.0, 1.0)).round() as i64;
+    let offset_y = ((slot_height - scaled_height as f32) * focal_y.clamp(0.0, 1.0)).round() as i64;
+    if values.mode == Fit::Cover {
+        return crop(
+            &mut scaled,
+            (-offset_x) as u32,
+            (-offset_y) as u32,
+            values.width,
+            values.height,
+        )
+        .to_image();
     }
+    let mut slot = RgbaImage::new(values.width, values.height);
+    overlay(&mut slot, &scaled, offset_x, offset_y);
+    slot
 }
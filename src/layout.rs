@@ -0,0 +1,223 @@
+use imageproc::drawing::text_size;
+use rusttype::{point, Font, Scale};
+
+use crate::text::{Align, Overflow, TextValues, VAlign};
+
+/// A single line of a text, positioned relative to the point where the text is placed.
+#[derive(Clone)]
+pub struct Line {
+    pub content: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+}
+
+/// The lines of a text after breaking and wrapping, as well as the size of the whole block.
+#[derive(Clone)]
+pub struct Block {
+    pub lines: Vec<Line>,
+    pub width: i32,
+    pub height: i32,
+    pub scale: Scale,
+    /// Vertical position of the box of the text relative to the position of the text, which moves
+    /// up with the lines when they are aligned on their baseline.
+    pub top: i32,
+}
+
+/// Sum of the glyph advances (kerning included) of the content, which is the distance the pen
+/// moves while drawing it.
+pub fn advance_width(font: &Font, scale: Scale, content: &str) -> f32 {
+    font.layout(content, scale, point(0.0, 0.0))
+        .last()
+        .map(|g| g.position().x + g.unpositioned().h_metrics().advance_width)
+        .unwrap_or(0.0)
+}
+
+/// Breaks the content on explicit line breaks and, when a maximum width is given, on the whitespace
+/// between the words so that each line fits within that width. A single word wider than the
+/// maximum width is kept on its own line.
+pub fn break_lines(
+    font: &Font,
+    scale: Scale,
+    content: &str,
+    max_width: Option<u32>,
+) -> Vec<String> {
+    let max_width = match max_width {
+        None => return content.lines().map(String::from).collect(),
+        Some(max_width) => max_width as f32,
+    };
+
+    let mut lines = Vec::new();
+    for paragraph in content.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if line.is_empty() {
+                line.push_str(word);
+                continue;
+            }
+            let candidate = format!("{} {}", line, word);
+            if advance_width(font, scale, &candidate) <= max_width {
+                line = candidate;
+            } else {
+                lines.push(line);
+                line = String::from(word);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Shortens the content, respecting the real width of each glyph, so that it fits within the
+/// maximum width followed by the ellipsis.
+fn ellipsize(
+    font: &Font,
+    scale: Scale,
+    content: &str,
+    ellipsis: &str,
+    max_width: Option<u32>,
+) -> String {
+    let available = match max_width {
+        None => return format!("{}{}", content, ellipsis),
+        Some(max_width) => max_width as f32 - advance_width(font, scale, ellipsis),
+    };
+
+    let mut end = 0;
+    for ((index, c), glyph) in
+        content
+            .char_indices()
+            .zip(font.layout(content, scale, point(0.0, 0.0)))
+    {
+        if glyph.position().x + glyph.unpositioned().h_metrics().advance_width > available {
+            break;
+        }
+        end = index + c.len_utf8();
+    }
+    format!("{}{}", content[..end].trim_end(), ellipsis)
+}
+
+/// Breaks the text into lines and positions them one below the other. When `limit` is true, the
+/// lines beyond the maximum number of lines or the maximum height of the text are handled according
+/// to its [`Overflow`].
+fn stack_lines(font: &Font, t: &TextValues, scale: Scale, limit: bool) -> Block {
+    let line_advance = (t.line_height * scale.y).round() as i32;
+    let mut contents = break_lines(font, scale, t.content, t.max_width);
+
+    if limit && *t.overflow != Overflow::Visible {
+        let mut visible = contents.len().min(t.max_lines.unwrap_or(usize::MAX));
+        if let Some(max_height) = t.max_height {
+            visible = (1..visible)
+                .find(|&index| {
+                    let (_, height) = text_size(scale, font, &contents[index]);
+                    index as i32 * line_advance + height > max_height as i32
+                })
+                .unwrap_or(visible);
+        }
+
+        if let Overflow::Ellipsis(ellipsis) = t.overflow {
+            if visible > 0 && visible < contents.len() {
+                let hidden = contents[visible - 1..].join(" ");
+                contents[visible - 1] = ellipsize(font, scale, &hidden, ellipsis, t.max_width);
+            }
+            for content in contents.iter_mut().take(visible) {
+                if let Some(max_width) = t.max_width {
+                    if advance_width(font, scale, content) > max_width as f32 {
+                        *content = ellipsize(font, scale, content, ellipsis, t.max_width);
+                    }
+                }
+            }
+        }
+        contents.truncate(visible);
+    }
+
+    let mut block = Block {
+        lines: Vec::new(),
+        width: 0,
+        height: 0,
+        scale,
+        top: 0,
+    };
+    for (index, content) in contents.into_iter().enumerate() {
+        let (width, height) = text_size(scale, font, &content);
+        let y = index as i32 * line_advance;
+
+        block.width = block.width.max(width);
+        block.height = y + height;
+        block.lines.push(Line {
+            content,
+            x: 0,
+            y,
+            width,
+        });
+    }
+    block
+}
+
+/// Searches the largest size between the minimum and maximum sizes of the text whose block, with
+/// all of its lines, fits within the given width and height. The minimum size is used when not even
+/// it fits.
+fn fit_scale(font: &Font, t: &TextValues, width: u32, height: u32) -> Scale {
+    let fits = |size: u32| {
+        let block = stack_lines(font, t, Scale::uniform(size as f32), false);
+        block.width <= width as i32
+            && block.height <= height as i32
+            && block.lines.len() <= t.max_lines.unwrap_or(usize::MAX)
+    };
+
+    let (mut low, mut high) = (t.min_size, t.max_size.max(t.min_size));
+    while low < high {
+        let middle = (low + high).div_ceil(2);
+        if fits(middle) {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    Scale::uniform(low as f32)
+}
+
+/// Resolves the size of the text, breaks it into lines and aligns each line relative to the box
+/// defined by the maximum width and height of the text or, when there is no box, to the position of
+/// the text.
+pub fn layout(font: &Font, t: &TextValues) -> Block {
+    let scale = match t.fit {
+        None => t.scale,
+        Some((width, height)) => fit_scale(font, t, width, height),
+    };
+    let mut block = stack_lines(font, t, scale, true);
+
+    let box_width = t.max_width.unwrap_or(0) as i32;
+    let box_height = t.max_height.unwrap_or(0) as i32;
+    let offset_y = match t.valign {
+        VAlign::Top => 0,
+        VAlign::Middle => (box_height - block.height) / 2,
+        VAlign::Baseline => -font.v_metrics(scale).ascent.round() as i32,
+        VAlign::Bottom => box_height - block.height,
+    };
+    if t.valign == VAlign::Baseline {
+        block.top = offset_y;
+    }
+    for line in block.lines.iter_mut() {
+        line.x = match t.align {
+            Align::Left => 0,
+            Align::Center => (box_width - line.width) / 2,
+            Align::Right => box_width - line.width,
+        };
+        line.y += offset_y;
+    }
+    block
+}
+
+/// Size of the part of the block that is drawn, which is cut to the box of the text when its
+/// overflow clips it.
+pub fn visible_size(t: &TextValues, block: &Block) -> (i32, i32) {
+    if *t.overflow != Overflow::Clip {
+        return (block.width, block.height);
+    }
+    (
+        t.max_width
+            .map_or(block.width, |width| block.width.min(width as i32)),
+        t.max_height
+            .map_or(block.height, |height| block.height.min(height as i32)),
+    )
+}
@@ -3,18 +3,43 @@
 //! Image Builder is a high-level library that uses the [image](https://crates.io/crates/image)
 //! crate as the engine to generate simple PNG images, but with convenience and simplicity.
 
+mod blend;
+mod effects;
+mod ellipse;
+mod error;
+mod exif;
+mod fill;
+mod group;
 mod image;
+mod layout;
+mod line;
+mod path;
 mod picture;
+mod polygon;
+mod polyline;
+mod raster;
 mod rect;
+mod stroke;
 mod text;
 
-pub use crate::image::FilterType;
+pub use crate::image::{FilterType, ImageOutputFormat};
 
 /// Contain some basic colors for quick use, as well as the structure that other colors must
 /// follow to be accepted by the library.
 pub mod colors;
 
 pub use crate::image::Image;
-pub use picture::Picture;
-pub use rect::Rect;
-pub use text::Text;
+pub use blend::BlendMode;
+pub use ellipse::Ellipse;
+pub use error::Error;
+pub use fill::Fill;
+pub use group::Group;
+pub use line::Line;
+pub use path::Path;
+pub use picture::{Filter, Fit, Mask, Picture, Repeat};
+pub use polygon::Polygon;
+pub use polyline::Polyline;
+pub use raster::FillRule;
+pub use rect::{BorderPosition, Rect};
+pub use stroke::{LineCap, LineJoin};
+pub use text::{Align, Overflow, Text, TextMetrics, VAlign};
@@ -0,0 +1,186 @@
+use image::Rgba;
+
+use crate::{
+    blend::BlendMode,
+    colors::{self, Color},
+    effects::Effects,
+    stroke::{LineCap, LineJoin, StrokeValues},
+};
+
+/// Specifications of a straight line between two points.
+#[derive(Clone)]
+pub struct Line {
+    from: (f32, f32),
+    to: (f32, f32),
+    width: u32,
+    color: Color,
+    cap: LineCap,
+    dash: Vec<u32>,
+    effects: Effects,
+}
+impl Line {
+    /// This method instantiates a specifications of a line from a point to another.
+    /// ## Example
+    /// ```
+    /// use image_builder::Line;
+    ///
+    /// Line::new((10.0, 10.0), (200.0, 100.0));
+    /// ```
+    pub fn new(from: (f32, f32), to: (f32, f32)) -> Line {
+        Line {
+            from,
+            to,
+            width: 1,
+            color: colors::BLACK,
+            cap: LineCap::Butt,
+            dash: Vec::new(),
+            effects: Effects::default(),
+        }
+    }
+
+    /// Define the width of the line in pixels.
+    /// ## Example
+    /// ```
+    /// use image_builder::Line;
+    ///
+    /// Line::new((10.0, 10.0), (200.0, 100.0))
+    ///     .width(4);
+    /// ```
+    pub fn width(&mut self, width: u32) -> Self {
+        self.width = width;
+        self.clone()
+    }
+
+    /// Define the color of the line.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Line, colors};
+    ///
+    /// Line::new((10.0, 10.0), (200.0, 100.0))
+    ///     .color(colors::RED);
+    /// ```
+    pub fn color(&mut self, color: Color) -> Self {
+        self.color = color;
+        self.clone()
+    }
+
+    /// Define the shape of the ends of the line. The default value is [`LineCap::Butt`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{Line, LineCap};
+    ///
+    /// Line::new((10.0, 10.0), (200.0, 100.0))
+    ///     .width(10)
+    ///     .cap(LineCap::Round);
+    /// ```
+    pub fn cap(&mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self.clone()
+    }
+
+    /// Draw the line as dashes, alternating the lengths in pixels of the visible and the hidden
+    /// parts. A pattern with an odd number of lengths is repeated to get an even number.
+    /// ## Example
+    /// ```
+    /// use image_builder::Line;
+    ///
+    /// Line::new((10.0, 10.0), (200.0, 10.0))
+    ///     .dash(&[8, 4]);
+    /// ```
+    pub fn dash(&mut self, pattern: &[u32]) -> Self {
+        self.dash = pattern.to_vec();
+        self.clone()
+    }
+
+    /// Rotate the line clockwise by the given degrees around its middle, or around the point defined
+    /// by [`Line::pivot`].
+    /// ## Example
+    /// ```
+    /// use image_builder::Line;
+    ///
+    /// Line::new((10.0, 50.0), (110.0, 50.0))
+    ///     .rotate(45.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the line is rotated, such as the center
+    /// of a clock for its hands.
+    /// ## Example
+    /// ```
+    /// use image_builder::Line;
+    ///
+    /// Line::new((100.0, 100.0), (100.0, 40.0))
+    ///     .width(4)
+    ///     .rotate(90.0)
+    ///     .pivot(100.0, 100.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the line, from `0.0` (invisible) to `1.0` (opaque).
+    /// ## Example
+    /// ```
+    /// use image_builder::Line;
+    ///
+    /// Line::new((0.0, 0.0), (100.0, 100.0))
+    ///     .opacity(0.5);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the color of the line is mixed with what is beneath it. Refer to the
+    /// [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{BlendMode, Line};
+    ///
+    /// Line::new((0.0, 0.0), (100.0, 100.0))
+    ///     .color([255, 255, 255, 255])
+    ///     .blend_mode(BlendMode::Difference);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the line, moved by the offset in pixels and softened by the blur radius.
+    /// ## Example
+    /// ```
+    /// use image_builder::Line;
+    ///
+    /// Line::new((0.0, 0.0), (100.0, 100.0))
+    ///     .width(4)
+    ///     .shadow(2, 2, 4, [0, 0, 0, 80]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
+}
+
+#[derive(Clone)]
+pub struct LineValues<'a> {
+    pub points: Vec<(f32, f32)>,
+    pub stroke: StrokeValues<'a>,
+    pub effects: Effects,
+}
+pub fn extract(line: &Line) -> LineValues<'_> {
+    LineValues {
+        points: vec![line.from, line.to],
+        stroke: StrokeValues {
+            width: line.width as f32,
+            color: Rgba(line.color),
+            cap: line.cap,
+            join: LineJoin::Miter,
+            dash: &line.dash,
+        },
+        effects: line.effects,
+    }
+}
@@ -1,17 +1,14 @@
 use std::fs;
-use std::io::Cursor;
 
-use image::io::Reader as ImageReader;
-use image::DynamicImage;
 use image_builder::{colors, FilterType, Image, Picture, Rect, Text};
 
-fn main() {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let width = 600;
     let height = 280;
     let mut image = Image::new(width, height, colors::GRAY);
 
-    let roboto_bold = fs::read("fonts/Roboto/Roboto-Bold.ttf").unwrap();
-    image.add_custom_font("Roboto bold", roboto_bold);
+    let roboto_bold = fs::read("fonts/Roboto/Roboto-Bold.ttf")?;
+    image.add_custom_font("Roboto bold", roboto_bold)?;
 
     image.add_rect(
         Rect::new()
@@ -27,33 +24,20 @@ fn main() {
             .color(colors::GRAY),
     );
 
-    let logo = fs::read("logo.png").unwrap();
-    let img: DynamicImage = ImageReader::new(Cursor::new(logo))
-        .with_guessed_format()
-        .expect("jpg or png")
-        .decode()
-        .unwrap();
     image.add_picture(
-        Picture::new(img)
-            .resize(134, 83, FilterType::Triangle)
+        Picture::from_path("logo.png")?
             .crop(41, 143, 536, 332)
+            .resize(134, 83, FilterType::Triangle)
             .position(233, 30),
     );
 
-    image.add_text(
-        Text::new("Image Builder")
-            .size(90)
-            .font("Roboto bold")
-            .position(60, 125)
-            .color([0, 0, 0, 100]),
-    );
-
     image.add_text(
         Text::new("Image Builder")
             .size(90)
             .font("Roboto bold")
             .position(55, 120)
-            .color(colors::PURPLE),
+            .color(colors::PURPLE)
+            .shadow(5, 5, 4, [0, 0, 0, 100]),
     );
 
     image.add_text(
@@ -63,5 +47,6 @@ fn main() {
             .color(colors::ORANGE),
     );
 
-    image.save("example.png");
+    image.save("example.png")?;
+    Ok(())
 }
@@ -0,0 +1,877 @@
+use std::f32::consts::PI;
+
+use image::Rgba;
+
+use crate::{
+    blend::BlendMode,
+    colors::{self, Color},
+    effects::Effects,
+    error::Error,
+    fill::Fill,
+    raster::{FillRule, Point, MAX_SEGMENTS, TOLERANCE},
+    stroke::{LineCap, LineJoin, StrokeValues},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Command {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo(Point, Point),
+    CubicTo(Point, Point, Point),
+    ArcTo {
+        radii: Point,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: Point,
+    },
+    Close,
+}
+
+/// Specifications of a vector shape made of lines and curves, like the paths of SVG files.
+///
+/// A path is made of commands that move a pen: [`Path::move_to`] starts a new contour, the other
+/// commands draw from the current point to a new one, and [`Path::close`] connects the contour back
+/// to its start. Paths can also be created from the `d` attribute of an SVG `<path>` element with
+/// [`Path::parse`]. The coordinates of the commands are scaled by [`Path::scale`] and then moved by
+/// [`Path::position`].
+/// ## Example
+/// ```
+/// use image_builder::{colors, Path};
+///
+/// Path::new() // A heart.
+///     .move_to(50.0, 30.0)
+///     .cubic_to(50.0, 0.0, 0.0, 0.0, 0.0, 30.0)
+///     .cubic_to(0.0, 60.0, 50.0, 80.0, 50.0, 100.0)
+///     .cubic_to(50.0, 80.0, 100.0, 60.0, 100.0, 30.0)
+///     .cubic_to(100.0, 0.0, 50.0, 0.0, 50.0, 30.0)
+///     .close()
+///     .color(colors::RED);
+/// ```
+#[derive(Clone)]
+pub struct Path {
+    commands: Vec<Command>,
+    position: (u32, u32),
+    scale: f32,
+    fill: Option<Fill>,
+    fill_rule: FillRule,
+    stroke: Option<(u32, Color)>,
+    cap: LineCap,
+    join: LineJoin,
+    dash: Vec<u32>,
+    effects: Effects,
+}
+impl Path {
+    /// This method instantiates a specifications of an empty path.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::new();
+    /// ```
+    pub fn new() -> Path {
+        Path {
+            commands: Vec::new(),
+            position: (0, 0),
+            scale: 1.0,
+            fill: Some(Fill::Solid(colors::BLACK)),
+            fill_rule: FillRule::NonZero,
+            stroke: None,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            dash: Vec::new(),
+            effects: Effects::default(),
+        }
+    }
+
+    /// This method instantiates a path from the `d` attribute of an SVG `<path>` element. All the
+    /// commands are supported (`M`, `L`, `H`, `V`, `C`, `S`, `Q`, `T`, `A` and `Z`), in their
+    /// absolute and relative forms. An [`Error::InvalidPath`] is returned when the data is invalid.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// let check = Path::parse("M4 12l5 5L20 6").unwrap();
+    /// assert!(Path::parse("M4 12 X").is_err());
+    /// ```
+    ///
+    /// The shorthands of SVG draw the same as their longer forms: relative commands, coordinates
+    /// repeated without a command, the control points reflected by `S` and `T`, flags of arcs
+    /// without separators, and numbers such as `1.5.5` (`1.5` and `.5`) or `1e1`.
+    /// ```
+    /// use image_builder::{colors, Image, Path};
+    ///
+    /// let render = |data: &str| {
+    ///     let mut image = Image::new(60, 60, colors::WHITE);
+    ///     image.add_path(Path::parse(data).unwrap());
+    ///     image.render().unwrap()
+    /// };
+    /// let same = |short: &str, long: &str| assert_eq!(render(short), render(long));
+    ///
+    /// same("M10 10 l20 0 l0 20z", "M10 10 L30 10 L30 30 Z");
+    /// same("M10 10 30 10 30 30z", "M10 10 L30 10 L30 30 Z");
+    /// same("m10 10 20 0 0 20z", "M10 10 L30 10 L30 30 Z");
+    /// same("M10 10 L30 10 30 30Z", "M10 10 L30 10 L30 30 Z");
+    /// same(
+    ///     "M10 30 C10 10 30 10 30 30 S50 50 50 30",
+    ///     "M10 30 C10 10 30 10 30 30 C30 50 50 50 50 30",
+    /// );
+    /// same("M10 20 Q20 0 30 20 T50 20", "M10 20 Q20 0 30 20 Q40 40 50 20");
+    /// same("M10 10 a10 10 0 0120 0", "M10 10 A10 10 0 0 1 30 10");
+    /// same("M1.5.5 L50 1e1 L1e1 50Z", "M1.5 0.5 L50 10 L10 50 Z");
+    /// ```
+    pub fn parse(data: &str) -> Result<Path, Error> {
+        let mut path = Path::new();
+        path.commands = parse(data)?;
+        Ok(path)
+    }
+
+    /// Start a new contour at the given point.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::new() // Two separate triangles.
+    ///     .move_to(0.0, 0.0)
+    ///     .line_to(20.0, 0.0)
+    ///     .line_to(10.0, 20.0)
+    ///     .close()
+    ///     .move_to(40.0, 0.0)
+    ///     .line_to(60.0, 0.0)
+    ///     .line_to(50.0, 20.0)
+    ///     .close();
+    /// ```
+    pub fn move_to(&mut self, x: f32, y: f32) -> Self {
+        self.commands.push(Command::MoveTo((x, y)));
+        self.clone()
+    }
+
+    /// Draw a straight line from the current point to the given point.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Path};
+    ///
+    /// Path::new() // A check mark.
+    ///     .move_to(4.0, 12.0)
+    ///     .line_to(9.0, 17.0)
+    ///     .line_to(20.0, 6.0)
+    ///     .no_fill()
+    ///     .stroke(2, colors::GREEN);
+    /// ```
+    pub fn line_to(&mut self, x: f32, y: f32) -> Self {
+        self.commands.push(Command::LineTo((x, y)));
+        self.clone()
+    }
+
+    /// Draw a quadratic Bézier curve from the current point to the point (x, y), using (cx, cy) as
+    /// the control point.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::new() // A leaf.
+    ///     .move_to(0.0, 50.0)
+    ///     .quad_to(50.0, 0.0, 100.0, 50.0)
+    ///     .quad_to(50.0, 100.0, 0.0, 50.0)
+    ///     .close();
+    /// ```
+    pub fn quad_to(&mut self, cx: f32, cy: f32, x: f32, y: f32) -> Self {
+        self.commands.push(Command::QuadTo((cx, cy), (x, y)));
+        self.clone()
+    }
+
+    /// Draw a cubic Bézier curve from the current point to the point (x, y), using (c1x, c1y) and
+    /// (c2x, c2y) as the control points.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Path};
+    ///
+    /// Path::new() // A wave.
+    ///     .move_to(0.0, 50.0)
+    ///     .cubic_to(25.0, 0.0, 75.0, 100.0, 100.0, 50.0)
+    ///     .no_fill()
+    ///     .stroke(3, colors::BLUE);
+    /// ```
+    pub fn cubic_to(&mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> Self {
+        self.commands
+            .push(Command::CubicTo((c1x, c1y), (c2x, c2y), (x, y)));
+        self.clone()
+    }
+
+    /// Draw an elliptical arc from the current point to the point (x, y), with the same parameters
+    /// as the `A` command of SVG: the radii of the ellipse, its rotation in degrees, and the flags
+    /// that choose which of the four possible arcs is drawn.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::new() // A half circle.
+    ///     .move_to(0.0, 50.0)
+    ///     .arc_to(50.0, 50.0, 0.0, false, true, 100.0, 50.0)
+    ///     .close();
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to(
+        &mut self,
+        rx: f32,
+        ry: f32,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        x: f32,
+        y: f32,
+    ) -> Self {
+        self.commands.push(Command::ArcTo {
+            radii: (rx, ry),
+            rotation,
+            large_arc,
+            sweep,
+            to: (x, y),
+        });
+        self.clone()
+    }
+
+    /// Connect the current contour back to its starting point.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Path};
+    ///
+    /// Path::new() // A closed triangle outline, with a corner joining its last side.
+    ///     .move_to(10.0, 10.0)
+    ///     .line_to(90.0, 10.0)
+    ///     .line_to(50.0, 80.0)
+    ///     .close()
+    ///     .no_fill()
+    ///     .stroke(4, colors::BLACK);
+    /// ```
+    pub fn close(&mut self) -> Self {
+        self.commands.push(Command::Close);
+        self.clone()
+    }
+
+    /// This method allows you to move the path within the image being constructed, the point (0, 0)
+    /// of the path is placed at the given position.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::parse("M4 12l5 5L20 6").unwrap()
+    ///     .position(100, 100);
+    /// ```
+    pub fn position(&mut self, x: u32, y: u32) -> Self {
+        self.position = (x, y);
+        self.clone()
+    }
+
+    /// Multiply the coordinates of the path by the given factor, useful to draw icons designed for
+    /// a small grid at a bigger size.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::parse("M4 12l5 5L20 6").unwrap() // A 24x24 icon drawn as 96x96.
+    ///     .scale(4.0);
+    /// ```
+    pub fn scale(&mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self.clone()
+    }
+
+    /// Define the color of the path. The default color is black.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Path, colors};
+    ///
+    /// Path::parse("M0 0H10V10Z").unwrap()
+    ///     .color(colors::BLUE);
+    /// ```
+    pub fn color(&mut self, color: Color) -> Self {
+        self.fill = Some(Fill::Solid(color));
+        self.clone()
+    }
+
+    /// Fill the path with a solid color or a gradient. The positions of a gradient are relative to
+    /// the top left corner of the box around the path. Refer to the [`Fill`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Fill, Path};
+    ///
+    /// Path::parse("M0 0H100V100Z").unwrap()
+    ///     .fill(Fill::LinearGradient {
+    ///         angle: 90.0,
+    ///         stops: vec![(0.0, colors::BLUE), (1.0, colors::GREEN)],
+    ///     });
+    /// ```
+    pub fn fill(&mut self, fill: Fill) -> Self {
+        self.fill = Some(fill);
+        self.clone()
+    }
+
+    /// Remove the fill of the path, so that only its stroke is drawn.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Path};
+    ///
+    /// Path::parse("M4 12l5 5L20 6").unwrap()
+    ///     .no_fill()
+    ///     .stroke(2, colors::GREEN);
+    /// ```
+    pub fn no_fill(&mut self) -> Self {
+        self.fill = None;
+        self.clone()
+    }
+
+    /// Define the rule that decides which areas are inside of a path whose contours overlap. The
+    /// default value is [`FillRule::NonZero`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{FillRule, Path};
+    ///
+    /// Path::parse("M0 0H100V100H0Z M25 25H75V75H25Z").unwrap() // A square with a hole.
+    ///     .fill_rule(FillRule::EvenOdd);
+    /// ```
+    ///
+    /// The center of a star drawn in one stroke is inside with the non-zero rule, but outside with
+    /// the even-odd rule.
+    /// ```
+    /// use image_builder::{colors, FillRule, Image, Path};
+    ///
+    /// let star = Path::parse("M50 5 L79 90 L5 37 L95 37 L21 90 Z").unwrap();
+    /// let center = |rule: FillRule| {
+    ///     let mut image = Image::new(100, 100, colors::WHITE);
+    ///     image.add_path(star.clone().fill_rule(rule));
+    ///     image.render().unwrap().get_pixel(50, 55).0
+    /// };
+    /// assert_eq!(center(FillRule::NonZero), colors::BLACK);
+    /// assert_eq!(center(FillRule::EvenOdd), colors::WHITE);
+    /// ```
+    pub fn fill_rule(&mut self, rule: FillRule) -> Self {
+        self.fill_rule = rule;
+        self.clone()
+    }
+
+    /// Draw a line with the given width in pixels and color along the path, centered on it. The
+    /// width is not affected by the scale of the path.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Path};
+    ///
+    /// Path::parse("M0 0H100V100Z").unwrap()
+    ///     .stroke(3, colors::BLACK);
+    /// ```
+    pub fn stroke(&mut self, width: u32, color: Color) -> Self {
+        self.stroke = Some((width, color));
+        self.clone()
+    }
+
+    /// Define the shape of the ends of the open contours of the stroke. The default value is
+    /// [`LineCap::Butt`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, LineCap, Path};
+    ///
+    /// Path::parse("M4 12l5 5L20 6").unwrap()
+    ///     .no_fill()
+    ///     .stroke(2, colors::GREEN)
+    ///     .cap(LineCap::Round);
+    /// ```
+    pub fn cap(&mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self.clone()
+    }
+
+    /// Define the shape of the corners of the stroke. The default value is [`LineJoin::Miter`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, LineJoin, Path};
+    ///
+    /// Path::parse("M4 12l5 5L20 6").unwrap()
+    ///     .no_fill()
+    ///     .stroke(2, colors::GREEN)
+    ///     .join(LineJoin::Round);
+    /// ```
+    pub fn join(&mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self.clone()
+    }
+
+    /// Draw the stroke as dashes, alternating the lengths in pixels of the visible and the hidden
+    /// parts. A pattern with an odd number of lengths is repeated to get an even number.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Path};
+    ///
+    /// Path::parse("M0 0H100V100H0Z").unwrap()
+    ///     .no_fill()
+    ///     .stroke(2, colors::BLACK)
+    ///     .dash(&[8, 4]);
+    /// ```
+    pub fn dash(&mut self, pattern: &[u32]) -> Self {
+        self.dash = pattern.to_vec();
+        self.clone()
+    }
+
+    /// Rotate the path clockwise by the given degrees around the center of the box around it, or
+    /// around the point defined by [`Path::pivot`].
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::parse("M 0 0 L 20 0 L 10 20 Z")
+    ///     .unwrap()
+    ///     .position(40, 40)
+    ///     .rotate(180.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the path is rotated.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::parse("M 0 0 L 20 0 L 10 20 Z")
+    ///     .unwrap()
+    ///     .rotate(45.0)
+    ///     .pivot(0.0, 0.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the path, from `0.0` (invisible) to `1.0` (opaque).
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::parse("M 0 0 L 20 0 L 10 20 Z")
+    ///     .unwrap()
+    ///     .opacity(0.5);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the colors of the path are mixed with what is beneath it. Refer to the
+    /// [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{BlendMode, Path};
+    ///
+    /// Path::parse("M 0 0 L 20 0 L 10 20 Z")
+    ///     .unwrap()
+    ///     .blend_mode(BlendMode::Exclusion);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the path, moved by the offset in pixels and softened by the blur radius.
+    /// ## Example
+    /// ```
+    /// use image_builder::Path;
+    ///
+    /// Path::parse("M 0 0 L 20 0 L 10 20 Z")
+    ///     .unwrap()
+    ///     .shadow(1, 2, 3, [0, 0, 0, 120]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads the numbers and the single character flags of the commands of the SVG path data.
+struct Parser<'a> {
+    data: &'a [u8],
+    index: usize,
+}
+impl<'a> Parser<'a> {
+    fn skip_separators(&mut self) {
+        while self.index < self.data.len()
+            && (self.data[self.index].is_ascii_whitespace() || self.data[self.index] == b',')
+        {
+            self.index += 1;
+        }
+    }
+
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let byte = *self.data.get(self.index)?;
+        if byte.is_ascii_alphabetic() {
+            self.index += 1;
+            Some(byte)
+        } else {
+            None
+        }
+    }
+
+    fn has_number(&mut self) -> bool {
+        self.skip_separators();
+        matches!(
+            self.data.get(self.index),
+            Some(b'0'..=b'9' | b'-' | b'+' | b'.')
+        )
+    }
+
+    fn number(&mut self) -> Result<f32, Error> {
+        self.skip_separators();
+        let start = self.index;
+        let mut seen_dot = false;
+        let mut seen_exponent = false;
+        if matches!(self.data.get(self.index), Some(b'-' | b'+')) {
+            self.index += 1;
+        }
+        while let Some(&byte) = self.data.get(self.index) {
+            match byte {
+                b'0'..=b'9' => {}
+                b'.' if !seen_dot && !seen_exponent => seen_dot = true,
+                b'e' | b'E' if !seen_exponent => {
+                    seen_exponent = true;
+                    if matches!(self.data.get(self.index + 1), Some(b'-' | b'+')) {
+                        self.index += 1;
+                    }
+                }
+                _ => break,
+            }
+            self.index += 1;
+        }
+        std::str::from_utf8(&self.data[start..self.index])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| Error::InvalidPath(format!("expected a number at position {}", start)))
+    }
+
+    fn flag(&mut self) -> Result<bool, Error> {
+        self.skip_separators();
+        let flag = match self.data.get(self.index) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => {
+                return Err(Error::InvalidPath(format!(
+                    "expected a flag at position {}",
+                    self.index
+                )))
+            }
+        };
+        self.index += 1;
+        Ok(flag)
+    }
+
+    fn point(&mut self, relative: bool, current: Point) -> Result<Point, Error> {
+        let (x, y) = (self.number()?, self.number()?);
+        Ok(if relative {
+            (current.0 + x, current.1 + y)
+        } else {
+            (x, y)
+        })
+    }
+}
+
+fn reflect(control: Point, around: Point) -> Point {
+    (2.0 * around.0 - control.0, 2.0 * around.1 - control.1)
+}
+
+fn parse(data: &str) -> Result<Vec<Command>, Error> {
+    let mut parser = Parser {
+        data: data.as_bytes(),
+        index: 0,
+    };
+    let mut commands = Vec::new();
+    let (mut current, mut start) = ((0.0, 0.0), (0.0, 0.0));
+    let mut last_control: Option<(u8, Point)> = None;
+
+    parser.skip_separators();
+    while parser.index < parser.data.len() {
+        let position = parser.index;
+        let letter = parser.command().ok_or_else(|| {
+            Error::InvalidPath(format!("expected a command at position {}", position))
+        })?;
+        let relative = letter.is_ascii_lowercase();
+        let mut first = true;
+
+        loop {
+            let command = match letter.to_ascii_uppercase() {
+                b'M' => {
+                    current = parser.point(relative, current)?;
+                    if first {
+                        start = current;
+                        Command::MoveTo(current)
+                    } else {
+                        Command::LineTo(current)
+                    }
+                }
+                b'L' => {
+                    current = parser.point(relative, current)?;
+                    Command::LineTo(current)
+                }
+                b'H' => {
+                    let x = parser.number()?;
+                    current.0 = if relative { current.0 + x } else { x };
+                    Command::LineTo(current)
+                }
+                b'V' => {
+                    let y = parser.number()?;
+                    current.1 = if relative { current.1 + y } else { y };
+                    Command::LineTo(current)
+                }
+                b'C' | b'S' => {
+                    let first_control = if letter.eq_ignore_ascii_case(&b'C') {
+                        parser.point(relative, current)?
+                    } else {
+                        match last_control {
+                            Some((b'C', control)) => reflect(control, current),
+                            _ => current,
+                        }
+                    };
+                    let second_control = parser.point(relative, current)?;
+                    current = parser.point(relative, current)?;
+                    last_control = Some((b'C', second_control));
+                    commands.push(Command::CubicTo(first_control, second_control, current));
+                    if !parser.has_number() {
+                        break;
+                    }
+                    first = false;
+                    continue;
+                }
+                b'Q' | b'T' => {
+                    let control = if letter.eq_ignore_ascii_case(&b'Q') {
+                        parser.point(relative, current)?
+                    } else {
+                        match last_control {
+                            Some((b'Q', control)) => reflect(control, current),
+                            _ => current,
+                        }
+                    };
+                    current = parser.point(relative, current)?;
+                    last_control = Some((b'Q', control));
+                    commands.push(Command::QuadTo(control, current));
+                    if !parser.has_number() {
+                        break;
+                    }
+                    first = false;
+                    continue;
+                }
+                b'A' => {
+                    let radii = (parser.number()?, parser.number()?);
+                    let rotation = parser.number()?;
+                    let large_arc = parser.flag()?;
+                    let sweep = parser.flag()?;
+                    current = parser.point(relative, current)?;
+                    Command::ArcTo {
+                        radii,
+                        rotation,
+                        large_arc,
+                        sweep,
+                        to: current,
+                    }
+                }
+                b'Z' => {
+                    current = start;
+                    Command::Close
+                }
+                _ => {
+                    return Err(Error::InvalidPath(format!(
+                        "unknown command \"{}\" at position {}",
+                        letter as char, position
+                    )))
+                }
+            };
+            commands.push(command);
+            last_control = None;
+            first = false;
+            if command == Command::Close || !parser.has_number() {
+                break;
+            }
+        }
+        parser.skip_separators();
+    }
+    Ok(commands)
+}
+
+pub fn quad_points(points: &mut Vec<Point>, from: Point, control: Point, to: Point) {
+    let second_difference =
+        (from.0 - 2.0 * control.0 + to.0).hypot(from.1 - 2.0 * control.1 + to.1);
+    let segments =
+        ((second_difference / (4.0 * TOLERANCE)).sqrt().ceil() as usize).clamp(1, MAX_SEGMENTS);
+    for segment in 1..=segments {
+        let t = segment as f32 / segments as f32;
+        let u = 1.0 - t;
+        points.push((
+            u * u * from.0 + 2.0 * u * t * control.0 + t * t * to.0,
+            u * u * from.1 + 2.0 * u * t * control.1 + t * t * to.1,
+        ));
+    }
+}
+
+pub fn cubic_points(points: &mut Vec<Point>, from: Point, c1: Point, c2: Point, to: Point) {
+    let second_difference = (from.0 - 2.0 * c1.0 + c2.0)
+        .hypot(from.1 - 2.0 * c1.1 + c2.1)
+        .max((c1.0 - 2.0 * c2.0 + to.0).hypot(c1.1 - 2.0 * c2.1 + to.1));
+    let segments =
+        ((0.75 * second_difference / TOLERANCE).sqrt().ceil() as usize).clamp(1, MAX_SEGMENTS);
+    for segment in 1..=segments {
+        let t = segment as f32 / segments as f32;
+        let u = 1.0 - t;
+        points.push((
+            u * u * u * from.0 + 3.0 * u * u * t * c1.0 + 3.0 * u * t * t * c2.0 + t * t * t * to.0,
+            u * u * u * from.1 + 3.0 * u * u * t * c1.1 + 3.0 * u * t * t * c2.1 + t * t * t * to.1,
+        ));
+    }
+}
+
+/// Appends the points of an SVG arc, converting its end points to the center of the ellipse and
+/// its angles as described in the appendix of the SVG specification.
+fn arc_points(
+    points: &mut Vec<Point>,
+    from: Point,
+    radii: Point,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Point,
+) {
+    let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+    if rx == 0.0 || ry == 0.0 || from == to {
+        points.push(to);
+        return;
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (dx, dy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+    let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        factor = -factor;
+    }
+    let (cx1, cy1) = (factor * rx * y1 / ry, -factor * ry * x1 / rx);
+    let center = (
+        cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0,
+        sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0,
+    );
+
+    let angle = |ux: f32, uy: f32| uy.atan2(ux);
+    let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    let step = 2.0 * (1.0 - TOLERANCE / rx.max(ry)).max(-1.0).acos();
+    let segments = ((delta.abs() / step).ceil() as usize).clamp(1, MAX_SEGMENTS);
+    for segment in 1..=segments {
+        let theta = start + delta * segment as f32 / segments as f32;
+        let (x, y) = (rx * theta.cos(), ry * theta.sin());
+        points.push((center.0 + cos * x - sin * y, center.1 + sin * x + cos * y));
+    }
+}
+
+#[derive(Clone)]
+pub struct PathValues<'a> {
+    pub contours: Vec<(Vec<Point>, bool)>,
+    pub fill: Option<&'a Fill>,
+    pub fill_rule: FillRule,
+    pub stroke: Option<StrokeValues<'a>>,
+    pub effects: Effects,
+}
+
+/// Converts the commands of the path into contours of straight segments in the coordinates of the
+/// image, each one with a flag telling whether it is closed.
+fn flatten(path: &Path) -> Vec<(Vec<Point>, bool)> {
+    let transform = |point: Point| {
+        (
+            point.0 * path.scale + path.position.0 as f32,
+            point.1 * path.scale + path.position.1 as f32,
+        )
+    };
+
+    let mut contours: Vec<(Vec<Point>, bool)> = Vec::new();
+    let mut points: Vec<Point> = Vec::new();
+    let mut current = transform((0.0, 0.0));
+    for command in path.commands.iter() {
+        if points.is_empty() && !matches!(command, Command::MoveTo(_)) {
+            points.push(current);
+        }
+        match *command {
+            Command::MoveTo(to) => {
+                if points.len() > 1 {
+                    contours.push((std::mem::take(&mut points), false));
+                }
+                current = transform(to);
+                points = vec![current];
+            }
+            Command::LineTo(to) => points.push(transform(to)),
+            Command::QuadTo(control, to) => {
+                quad_points(&mut points, current, transform(control), transform(to))
+            }
+            Command::CubicTo(c1, c2, to) => cubic_points(
+                &mut points,
+                current,
+                transform(c1),
+                transform(c2),
+                transform(to),
+            ),
+            Command::ArcTo {
+                radii,
+                rotation,
+                large_arc,
+                sweep,
+                to,
+            } => arc_points(
+                &mut points,
+                current,
+                (radii.0 * path.scale, radii.1 * path.scale),
+                rotation,
+                large_arc,
+                sweep,
+                transform(to),
+            ),
+            Command::Close => {
+                current = points[0];
+                contours.push((std::mem::take(&mut points), true));
+                continue;
+            }
+        }
+        current = *points.last().unwrap_or(&current);
+    }
+    if points.len() > 1 {
+        contours.push((points, false));
+    }
+    contours
+}
+
+pub fn extract(path: &Path) -> PathValues<'_> {
+    PathValues {
+        contours: flatten(path),
+        fill: path.fill.as_ref(),
+        fill_rule: path.fill_rule,
+        stroke: path.stroke.map(|(width, color)| StrokeValues {
+            width: width as f32,
+            color: Rgba(color),
+            cap: path.cap,
+            join: path.join,
+            dash: &path.dash,
+        }),
+        effects: path.effects,
+    }
+}
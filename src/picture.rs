@@ -1,5 +1,99 @@
+use std::{fmt, fs, io::Read, path::Path, sync::Arc};
+
 use image::imageops::FilterType;
-use image::DynamicImage;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+
+use crate::{blend::BlendMode, colors::Color, effects::Effects, error::Error, exif};
+
+/// Shapes that cut out a picture. See [`Picture::mask`].
+#[derive(Clone, Debug)]
+pub enum Mask {
+    /// Keeps the circle inscribed in the picture, or the ellipse when the picture is not square.
+    Circle,
+    /// Rounds the corners of the picture with the given radius in pixels.
+    RoundedRect(u32),
+    /// Uses the alpha channel of another image as the opacity of the picture, resizing it to the
+    /// size of the picture when needed.
+    Alpha(DynamicImage),
+}
+
+/// How a picture is scaled to fill a slot of a fixed size. See [`Picture::fit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fit {
+    /// Scales the picture, keeping its aspect ratio, until it covers the whole slot, cutting off
+    /// the parts that overflow.
+    Cover,
+    /// Scales the picture, keeping its aspect ratio, until it fits entirely inside the slot,
+    /// leaving transparent bars on the remaining space.
+    Contain,
+    /// Stretches the picture to the exact size of the slot, distorting it if needed.
+    Fill,
+    /// Like `Contain`, but never makes the picture bigger than its original size.
+    ScaleDown,
+}
+
+/// Color adjustments for pictures, with the same meaning and amounts as the CSS filter functions.
+/// See [`Picture::filter`].
+/// ## Example
+/// ```
+/// use image::{DynamicImage, RgbaImage};
+/// use image_builder::{Filter, Image, Picture};
+///
+/// let filtered = |filters: &[Filter]| {
+///     let pixel = RgbaImage::from_pixel(1, 1, image::Rgba([200, 100, 40, 255]));
+///     let mut picture = Picture::new(DynamicImage::ImageRgba8(pixel));
+///     for filter in filters {
+///         picture = picture.filter(*filter);
+///     }
+///     let mut image = Image::new(1, 1, [0, 0, 0, 0]);
+///     image.add_picture(picture);
+///     image.render().unwrap().get_pixel(0, 0).0
+/// };
+/// let [red, green, blue, _] = filtered(&[Filter::Grayscale(1.0)]);
+/// assert!(red == green && green == blue);
+/// assert_eq!(filtered(&[Filter::Invert(1.0)]), [55, 155, 215, 255]);
+/// assert_eq!(filtered(&[Filter::HueRotate(180.0)]), [34, 134, 194, 255]);
+/// // Filters apply in the order they are added.
+/// let brighten_then_invert = [Filter::Brightness(2.0), Filter::Invert(1.0)];
+/// let invert_then_brighten = [Filter::Invert(1.0), Filter::Brightness(2.0)];
+/// assert_eq!(filtered(&brighten_then_invert), [0, 55, 175, 255]);
+/// assert_eq!(filtered(&invert_then_brighten), [110, 255, 255, 255]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Filter {
+    /// Converts the picture to shades of gray, from `0.0` (unchanged) to `1.0` (fully gray).
+    Grayscale(f32),
+    /// Gives the picture the brown tones of old photos, from `0.0` (unchanged) to `1.0`.
+    Sepia(f32),
+    /// Multiplies the colors, where `1.0` keeps the picture unchanged, lower values darken it and
+    /// higher values brighten it.
+    Brightness(f32),
+    /// Adjusts the difference between light and dark colors, where `1.0` keeps the picture
+    /// unchanged and `0.0` turns it gray.
+    Contrast(f32),
+    /// Adjusts the intensity of the colors, where `1.0` keeps the picture unchanged, `0.0` turns it
+    /// gray and higher values make it more vivid.
+    Saturation(f32),
+    /// Rotates the hue of the colors by the given degrees.
+    HueRotate(f32),
+    /// Inverts the colors, from `0.0` (unchanged) to `1.0` (fully inverted).
+    Invert(f32),
+}
+
+/// How a picture is repeated by [`Picture::tile`] or when it is used as a [`crate::Fill`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Repeat {
+    /// Repeats the picture in both directions.
+    #[default]
+    Repeat,
+    /// Repeats the picture only horizontally, in a single row.
+    RepeatX,
+    /// Repeats the picture only vertically, in a single column.
+    RepeatY,
+    /// Repeats the picture in both directions, mirroring every other copy so that the edges of
+    /// neighbor copies match.
+    Mirror,
+}
 
 /// External images.
 ///
@@ -7,8 +101,8 @@ use image::DynamicImage;
 /// included in the image being built. It allows for cropping, resizing, and positioning to compose
 /// the final image.
 ///
-/// **⚠️ It's important to note that if there is both cropping and resizing on the same
-/// image, the library will always perform cropping first, and then resizing.** ⚠️
+/// Transformations such as cropping and resizing are applied in the same order as their methods
+/// are called, and each one can be used more than once.
 /// ## Example
 /// ```
 /// # use image::io::Reader as ImageReader;
@@ -18,21 +112,29 @@ use image::DynamicImage;
 ///
 /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
 /// Picture::new(image)
-///     .resize(100, 100, FilterType::Triangle) // Resizing is specified here, but the library will first perform the cropping below, and then this resizing.
-///     .crop(50, 50, 200, 200);
+///     .crop(50, 50, 200, 200)
+///     .resize(100, 100, FilterType::Triangle);
 /// ```
 /// In the example above, an image of 300x300 pixels was imported, a square of 200x200 pixels was
 /// cropped, and then this cropped portion was resized by half, resulting in an image of 100x100 pixels.
-#[derive(Clone)]
+/// Calling `resize` before `crop` would instead crop the resized image.
+#[derive(Clone, Debug)]
 pub struct Picture {
-    img: image::DynamicImage,
-    crop: Option<(u32, u32, u32, u32)>,
-    resize: Option<(u32, u32, FilterType)>,
+    img: Source,
+    transforms: Vec<Transform>,
     position: (u32, u32),
+    mask: Option<Mask>,
+    focal_point: (f32, f32),
+    orientation: u16,
+    auto_orient: bool,
+    repeat: Repeat,
+    spacing: (u32, u32),
+    effects: Effects,
 }
 impl Picture {
-    /// This method instantiates an external image using the file path of the image
-    /// and positions it at the point (0,0) of the image being built.
+    /// This method instantiates an external image from an already decoded image and positions it
+    /// at the point (0,0) of the image being built. To load an image file or its bytes, see
+    /// [`Picture::from_path`] and [`Picture::from_bytes`].
     /// ## Example
     /// ```
     /// use image_builder::Picture;
@@ -42,14 +144,88 @@ impl Picture {
     /// Picture::new(image);
     /// ```
     pub fn new(img: DynamicImage) -> Picture {
+        Picture::with_source(Source::Decoded(img))
+    }
+
+    fn with_source(img: Source) -> Picture {
         Picture {
             img,
-            resize: None,
-            crop: None,
+            transforms: Vec::new(),
             position: (0, 0),
+            mask: None,
+            focal_point: (0.5, 0.5),
+            orientation: 1,
+            auto_orient: true,
+            repeat: Repeat::Repeat,
+            spacing: (0, 0),
+            effects: Effects::default(),
         }
     }
 
+    /// Loads a picture from an image file, guessing its format from its content. Photos taken by
+    /// phones are turned upright according to their EXIF orientation, see [`Picture::auto_orient`].
+    /// An [`Error::Io`] is returned if the file cannot be read and an [`Error::Decode`] if its format
+    /// is not supported.
+    ///
+    /// The image is only decoded when it is rendered, so a corrupted file makes the rendering return
+    /// an [`Error::Decode`].
+    /// ## Example
+    /// ```
+    /// use image_builder::Picture;
+    ///
+    /// let logo = Picture::from_path("logo.png").unwrap();
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Picture, Error> {
+        Picture::from_bytes(&fs::read(path)?)
+    }
+
+    /// Loads a picture from the bytes of an encoded image, such as an upload, guessing its format
+    /// from its content. Like [`Picture::from_path`], the EXIF orientation of the image is applied
+    /// and the image is only decoded when it is rendered.
+    /// ## Example
+    /// ```
+    /// use image_builder::Picture;
+    ///
+    /// let bytes = std::fs::read("logo.png").unwrap();
+    /// let logo = Picture::from_bytes(&bytes).unwrap();
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Picture, Error> {
+        let format = image::guess_format(bytes).map_err(Error::Decode)?;
+        let mut picture = Picture::with_source(Source::Encoded(Arc::from(bytes), format));
+        picture.orientation = exif::orientation(bytes).unwrap_or(1);
+        Ok(picture)
+    }
+
+    /// Loads a picture from any reader, such as the body of an HTTP response, reading it to the
+    /// end. Like [`Picture::from_bytes`], the format is guessed from the content and the image is
+    /// only decoded when it is rendered.
+    /// ## Example
+    /// ```
+    /// use image_builder::Picture;
+    ///
+    /// let file = std::fs::File::open("logo.png").unwrap();
+    /// let logo = Picture::from_reader(file).unwrap();
+    /// ```
+    pub fn from_reader(mut reader: impl Read) -> Result<Picture, Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Picture::from_bytes(&bytes)
+    }
+
+    /// Define whether the EXIF orientation read by [`Picture::from_path`] and
+    /// [`Picture::from_bytes`] is applied before the other transformations, which is the default.
+    /// Turn it off to keep the pixels as they are stored in the file.
+    /// ## Example
+    /// ```
+    /// use image_builder::Picture;
+    ///
+    /// let raw = Picture::from_path("logo.png").unwrap().auto_orient(false);
+    /// ```
+    pub fn auto_orient(&mut self, enabled: bool) -> Self {
+        self.auto_orient = enabled;
+        self.clone()
+    }
+
     /// This method allows resizing an image by specifying the desired new height, width and [`FilterType`].
     /// ## Example
     /// ```rust
@@ -62,12 +238,17 @@ impl Picture {
     ///     .resize(200, 100, FilterType::Triangle);
     /// ```
     pub fn resize(&mut self, width: u32, height: u32, filter: FilterType) -> Self {
-        self.resize = Some((width, height, filter));
+        self.transforms.push(Transform::Resize(ResizeValues {
+            nwidth: width,
+            nheight: height,
+            filter,
+        }));
         self.clone()
     }
 
     /// Use this method to crop an imported image by providing the starting point of the crop (x, y),
-    /// as well as the desired height and width to be cropped.
+    /// as well as the desired height and width to be cropped. A crop that goes beyond the picture
+    /// makes the rendering fail with [`Error::InvalidGeometry`].
     /// ## Example
     /// ```
     /// # use image::io::Reader as ImageReader;
@@ -77,8 +258,23 @@ impl Picture {
     /// Picture::new(image)
     ///     .crop(50, 50, 200, 200);
     /// ```
+    ///
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::{colors, Error, Image, Picture};
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// let mut canvas = Image::new(100, 100, colors::WHITE);
+    /// canvas.add_picture(Picture::new(image).crop(u32::MAX, 0, 5, 5));
+    /// assert!(matches!(canvas.render(), Err(Error::InvalidGeometry(_))));
+    /// ```
     pub fn crop(&mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
-        self.crop = Some((x, y, width, height));
+        self.transforms.push(Transform::Crop(CropValues {
+            x,
+            y,
+            width,
+            height,
+        }));
         self.clone()
     }
 
@@ -96,9 +292,340 @@ impl Picture {
         self.position = (x, y);
         self.clone()
     }
+
+    /// Cut out the picture with a [`Mask`], with anti-aliased edges. The mask is applied after
+    /// all the transformations, so it follows the final size of the picture.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// # use image_builder::FilterType;
+    /// use image_builder::{Mask, Picture};
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // A round avatar.
+    ///     .crop(0, 0, 280, 280)
+    ///     .resize(96, 96, FilterType::Triangle)
+    ///     .mask(Mask::Circle);
+    /// ```
+    pub fn mask(&mut self, mask: Mask) -> Self {
+        self.mask = Some(mask);
+        self.clone()
+    }
+
+    /// This method scales the picture into a slot of the given width and height without manual
+    /// math, according to the [`Fit`] mode and based on the size of the picture after the
+    /// transformations called before it. The resulting picture always has the size of the slot.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::{Fit, Picture};
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .fit(200, 200, Fit::Cover);
+    /// ```
+    pub fn fit(&mut self, width: u32, height: u32, fit: Fit) -> Self {
+        self.transforms.push(Transform::Fit(FitValues {
+            width,
+            height,
+            mode: fit,
+        }));
+        self.clone()
+    }
+
+    /// Define the point of the picture that is kept in view by [`Fit::Cover`], or where the
+    /// picture is placed inside the slot by [`Fit::Contain`] and [`Fit::ScaleDown`]. The point is
+    /// given as fractions of the width and height, where `(0.0, 0.0)` is the top left corner and
+    /// `(1.0, 1.0)` is the bottom right corner. The default value is the center, `(0.5, 0.5)`, and
+    /// the same point is used by every fit of the picture.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::{Fit, Picture};
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // Keeps the top of a portrait photo, where the face usually is.
+    ///     .fit(200, 200, Fit::Cover)
+    ///     .focal_point(0.5, 0.2);
+    /// ```
+    pub fn focal_point(&mut self, x: f32, y: f32) -> Self {
+        self.focal_point = (x, y);
+        self.clone()
+    }
+
+    /// Blur the picture with a gaussian blur of the given standard deviation in pixels, such as a
+    /// soft background photo behind a text. Like the other transformations, it is applied in the
+    /// order it is called, so blurring after resizing down is faster.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// # use image_builder::FilterType;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .resize(400, 300, FilterType::Triangle)
+    ///     .blur(8.0);
+    /// ```
+    pub fn blur(&mut self, sigma: f32) -> Self {
+        self.transforms.push(Transform::Blur(sigma));
+        self.clone()
+    }
+
+    /// Blur the picture by averaging each pixel with its neighbors up to the given radius in
+    /// pixels. It is sharper than [`Picture::blur`], with a blocky look.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .box_blur(4);
+    /// ```
+    pub fn box_blur(&mut self, radius: u32) -> Self {
+        self.transforms.push(Transform::BoxBlur(radius));
+        self.clone()
+    }
+
+    /// Adjust the colors of the picture with a [`Filter`]. Filters can be combined by calling this
+    /// method again, and are applied in order together with the other transformations.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::{Filter, Picture};
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // A faded vintage look.
+    ///     .filter(Filter::Sepia(0.8))
+    ///     .filter(Filter::Contrast(0.9))
+    ///     .filter(Filter::Brightness(1.1));
+    /// ```
+    pub fn filter(&mut self, filter: Filter) -> Self {
+        self.transforms.push(Transform::Filter(filter));
+        self.clone()
+    }
+
+    /// Mirror the picture from left to right, such as a selfie taken by a front camera.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .flip_horizontal();
+    /// ```
+    pub fn flip_horizontal(&mut self) -> Self {
+        self.transforms.push(Transform::FlipHorizontal);
+        self.clone()
+    }
+
+    /// Mirror the picture from top to bottom.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .flip_vertical();
+    /// ```
+    pub fn flip_vertical(&mut self) -> Self {
+        self.transforms.push(Transform::FlipVertical);
+        self.clone()
+    }
+
+    /// Turn the picture a quarter clockwise, swapping its width and height. Unlike
+    /// [`Picture::rotate`], no pixel is resampled, and later transformations see the turned
+    /// picture.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// # use image_builder::FilterType;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // The width of the result is 100 pixels.
+    ///     .rotate90()
+    ///     .resize(100, 200, FilterType::Triangle);
+    /// ```
+    pub fn rotate90(&mut self) -> Self {
+        self.transforms.push(Transform::Rotate90);
+        self.clone()
+    }
+
+    /// Turn the picture upside down, without resampling its pixels.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .rotate180();
+    /// ```
+    pub fn rotate180(&mut self) -> Self {
+        self.transforms.push(Transform::Rotate180);
+        self.clone()
+    }
+
+    /// Turn the picture a quarter counterclockwise, swapping its width and height, without
+    /// resampling its pixels.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .rotate270();
+    /// ```
+    pub fn rotate270(&mut self) -> Self {
+        self.transforms.push(Transform::Rotate270);
+        self.clone()
+    }
+
+    /// Repeat the picture until it covers an area of the given width and height, which becomes the
+    /// size of the picture, like a watermark pattern over a whole image. The copies are placed
+    /// according to [`Picture::repeat`] and [`Picture::spacing`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{FilterType, Picture};
+    /// use image::io::Reader as ImageReader;
+    ///
+    /// let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // A watermark over an image of 600x400 pixels.
+    ///     .resize(60, 30, FilterType::Triangle)
+    ///     .tile(600, 400)
+    ///     .spacing(40, 40)
+    ///     .opacity(0.2);
+    /// ```
+    pub fn tile(&mut self, area_width: u32, area_height: u32) -> Self {
+        self.transforms
+            .push(Transform::Tile(area_width, area_height));
+        self.clone()
+    }
+
+    /// Define how the picture is repeated by [`Picture::tile`] or when it is used as a
+    /// [`crate::Fill::Pattern`]. The default value is [`Repeat::Repeat`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{Picture, Repeat};
+    /// use image::io::Reader as ImageReader;
+    ///
+    /// let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // A border along the top of an image.
+    ///     .tile(600, 40)
+    ///     .repeat(Repeat::RepeatX);
+    /// ```
+    pub fn repeat(&mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self.clone()
+    }
+
+    /// Define the horizontal and vertical space in pixels left empty between the copies of the
+    /// picture when it is repeated.
+    /// ## Example
+    /// ```
+    /// use image_builder::Picture;
+    /// use image::io::Reader as ImageReader;
+    ///
+    /// let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .tile(600, 400)
+    ///     .spacing(20, 10);
+    /// ```
+    pub fn spacing(&mut self, horizontal: u32, vertical: u32) -> Self {
+        self.spacing = (horizontal, vertical);
+        self.clone()
+    }
+
+    /// Rotate the picture clockwise by the given degrees around its center, or around the point
+    /// defined by [`Picture::pivot`], after all of its transformations and its mask. The corners left
+    /// empty by the rotation are transparent.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // A tilted photo.
+    ///     .position(100, 100)
+    ///     .rotate(-8.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the picture is rotated.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .position(100, 100)
+    ///     .rotate(15.0)
+    ///     .pivot(100.0, 100.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the picture, from `0.0` (invisible) to `1.0` (opaque).
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::Picture;
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // A faded background photo.
+    ///     .opacity(0.25);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the colors of the picture are mixed with what is beneath it. Refer to the
+    /// [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::{BlendMode, Picture};
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image) // A texture that darkens the background.
+    ///     .blend_mode(BlendMode::Multiply);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the picture, moved by the offset in pixels and softened by the blur
+    /// radius. The shadow follows the transparent areas and the mask of the picture.
+    /// ## Example
+    /// ```
+    /// # use image::io::Reader as ImageReader;
+    /// use image_builder::{Mask, Picture};
+    ///
+    /// # let image = ImageReader::open("example.png").unwrap().decode().unwrap();
+    /// Picture::new(image)
+    ///     .mask(Mask::Circle)
+    ///     .shadow(0, 6, 12, [0, 0, 0, 80]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct CropValues {
     pub x: u32,
     pub y: u32,
@@ -106,42 +633,142 @@ pub struct CropValues {
     pub height: u32,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ResizeValues {
     pub nwidth: u32,
     pub nheight: u32,
     pub filter: FilterType,
 }
 
+#[derive(Clone, Debug)]
+pub struct FitValues {
+    pub width: u32,
+    pub height: u32,
+    pub mode: Fit,
+}
+
+/// A step of the transformation of a picture, kept in the order the methods were called.
+#[derive(Clone, Debug)]
+pub enum Transform {
+    Crop(CropValues),
+    Resize(ResizeValues),
+    Fit(FitValues),
+    Blur(f32),
+    BoxBlur(u32),
+    Filter(Filter),
+    FlipHorizontal,
+    FlipVertical,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Tile(u32, u32),
+}
+
 #[derive(Clone)]
 pub struct PictureValues<'a> {
-    pub img: &'a DynamicImage,
+    pub img: &'a Source,
     pub x: i64,
     pub y: i64,
-    pub crop: Option<CropValues>,
-    pub resize: Option<ResizeValues>,
+    pub transforms: &'a [Transform],
+    pub mask: Option<&'a Mask>,
+    pub focal_point: (f32, f32),
+    pub orientation: Vec<Transform>,
+    pub repeat: Repeat,
+    pub spacing: (u32, u32),
+    pub effects: Effects,
+}
+/// The image of a picture, either already decoded or still encoded, to be decoded when rendered.
+#[derive(Clone)]
+pub enum Source {
+    Decoded(DynamicImage),
+    Encoded(Arc<[u8]>, ImageFormat),
+}
+impl fmt::Debug for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Decoded(img) => write!(f, "Decoded({}x{})", img.width(), img.height()),
+            Source::Encoded(bytes, format) => {
+                write!(f, "Encoded({:?}, {} bytes)", format, bytes.len())
+            }
+        }
+    }
+}
+
+/// Decodes the image of a picture when needed.
+pub fn decode(source: &Source) -> Result<RgbaImage, Error> {
+    match source {
+        Source::Decoded(img) => Ok(img.to_rgba8()),
+        Source::Encoded(bytes, format) => Ok(image::load_from_memory_with_format(bytes, *format)
+            .map_err(Error::Decode)?
+            .to_rgba8()),
+    }
 }
-pub fn extract(picture: &Picture) -> PictureValues {
+
+/// Color of a picture repeated over an infinite plane at a point of that plane, where the first copy
+/// of the picture has its top left corner at the origin.
+pub fn tile_pixel(
+    picture: &RgbaImage,
+    x: i64,
+    y: i64,
+    repeat: Repeat,
+    spacing: (u32, u32),
+) -> Rgba<u8> {
+    let (width, height) = (picture.width() as i64, picture.height() as i64);
+    if width == 0 || height == 0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    let (cell_width, cell_height) = (width + spacing.0 as i64, height + spacing.1 as i64);
+    let (column, row) = (x.div_euclid(cell_width), y.div_euclid(cell_height));
+    let (mut px, mut py) = (x.rem_euclid(cell_width), y.rem_euclid(cell_height));
+    let outside = match repeat {
+        Repeat::RepeatX => row != 0,
+        Repeat::RepeatY => column != 0,
+        Repeat::Repeat | Repeat::Mirror => false,
+    };
+    // Points in the spacing between copies are left transparent.
+    if outside || px >= width || py >= height {
+        return Rgba([0, 0, 0, 0]);
+    }
+    if repeat == Repeat::Mirror {
+        if column % 2 != 0 {
+            px = width - 1 - px;
+        }
+        if row % 2 != 0 {
+            py = height - 1 - py;
+        }
+    }
+    *picture.get_pixel(px as u32, py as u32)
+}
+
+/// Steps that turn a picture with the given EXIF orientation upright.
+fn orientation_steps(orientation: u16) -> Vec<Transform> {
+    match orientation {
+        2 => vec![Transform::FlipHorizontal],
+        3 => vec![Transform::Rotate180],
+        4 => vec![Transform::FlipVertical],
+        5 => vec![Transform::Rotate90, Transform::FlipHorizontal],
+        6 => vec![Transform::Rotate90],
+        7 => vec![Transform::Rotate270, Transform::FlipHorizontal],
+        8 => vec![Transform::Rotate270],
+        _ => Vec::new(),
+    }
+}
+
+pub fn extract(picture: &Picture) -> PictureValues<'_> {
     PictureValues {
         img: &picture.img,
         x: picture.position.0 as i64,
         y: picture.position.1 as i64,
-        crop: match picture.crop {
-            None => None,
-            Some(values) => Some(CropValues {
-                x: values.0,
-                y: values.1,
-                width: values.2,
-                height: values.3,
-            }),
-        },
-        resize: match picture.resize {
-            None => None,
-            Some(values) => Some(ResizeValues {
-                nwidth: values.0,
-                nheight: values.1,
-                filter: values.2,
-            }),
+        transforms: &picture.transforms,
+        mask: picture.mask.as_ref(),
+        focal_point: picture.focal_point,
+        repeat: picture.repeat,
+        spacing: picture.spacing,
+        orientation: if picture.auto_orient {
+            orientation_steps(picture.orientation)
+        } else {
+            Vec::new()
         },
+        effects: picture.effects,
     }
 }
@@ -0,0 +1,218 @@
+use image::Rgba;
+
+use crate::{
+    blend::BlendMode,
+    colors::{self, Color},
+    effects::Effects,
+    fill::Fill,
+    stroke::{LineCap, LineJoin, StrokeValues},
+};
+
+/// Specifications of a closed shape made of straight edges.
+#[derive(Clone)]
+pub struct Polygon {
+    points: Vec<(f32, f32)>,
+    fill: Option<Fill>,
+    stroke: Option<(u32, Color)>,
+    join: LineJoin,
+    dash: Vec<u32>,
+    effects: Effects,
+}
+impl Polygon {
+    /// This method instantiates a specifications of a polygon whose vertices are the given points.
+    /// The last point is connected back to the first one.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polygon;
+    ///
+    /// Polygon::new(&[(60.0, 20.0), (110.0, 100.0), (10.0, 100.0)]);
+    /// ```
+    pub fn new(points: &[(f32, f32)]) -> Polygon {
+        Polygon {
+            points: points.to_vec(),
+            fill: Some(Fill::Solid(colors::GREEN)),
+            stroke: None,
+            join: LineJoin::Miter,
+            dash: Vec::new(),
+            effects: Effects::default(),
+        }
+    }
+
+    /// Define the color of the polygon.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Polygon, colors};
+    ///
+    /// Polygon::new(&[(60.0, 20.0), (110.0, 100.0), (10.0, 100.0)])
+    ///     .color(colors::RED);
+    /// ```
+    pub fn color(&mut self, color: Color) -> Self {
+        self.fill = Some(Fill::Solid(color));
+        self.clone()
+    }
+
+    /// Fill the polygon with a solid color or a gradient. The positions of a gradient are relative
+    /// to the top left corner of the box around the polygon. Refer to the [`Fill`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Fill, Polygon};
+    ///
+    /// Polygon::new(&[(60.0, 20.0), (110.0, 100.0), (10.0, 100.0)])
+    ///     .fill(Fill::LinearGradient {
+    ///         angle: 180.0,
+    ///         stops: vec![(0.0, colors::YELLOW), (1.0, colors::RED)],
+    ///     });
+    /// ```
+    pub fn fill(&mut self, fill: Fill) -> Self {
+        self.fill = Some(fill);
+        self.clone()
+    }
+
+    /// Remove the fill of the polygon, so that only its outline is drawn.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Polygon};
+    ///
+    /// Polygon::new(&[(60.0, 20.0), (110.0, 100.0), (10.0, 100.0)])
+    ///     .no_fill()
+    ///     .stroke(2, colors::BLACK);
+    /// ```
+    pub fn no_fill(&mut self) -> Self {
+        self.fill = None;
+        self.clone()
+    }
+
+    /// Draw a line with the given width in pixels and color along the edges of the polygon,
+    /// centered on them.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Polygon};
+    ///
+    /// Polygon::new(&[(60.0, 20.0), (110.0, 100.0), (10.0, 100.0)])
+    ///     .stroke(4, colors::BLACK);
+    /// ```
+    pub fn stroke(&mut self, width: u32, color: Color) -> Self {
+        self.stroke = Some((width, color));
+        self.clone()
+    }
+
+    /// Define the shape of the corners of the stroke. The default value is [`LineJoin::Miter`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, LineJoin, Polygon};
+    ///
+    /// Polygon::new(&[(60.0, 20.0), (110.0, 100.0), (10.0, 100.0)])
+    ///     .stroke(8, colors::BLACK)
+    ///     .join(LineJoin::Round);
+    /// ```
+    pub fn join(&mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self.clone()
+    }
+
+    /// Draw the stroke as dashes, alternating the lengths in pixels of the visible and the hidden
+    /// parts. A pattern with an odd number of lengths is repeated to get an even number.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Polygon};
+    ///
+    /// Polygon::new(&[(60.0, 20.0), (110.0, 100.0), (10.0, 100.0)])
+    ///     .stroke(2, colors::BLACK)
+    ///     .dash(&[6, 3]);
+    /// ```
+    pub fn dash(&mut self, pattern: &[u32]) -> Self {
+        self.dash = pattern.to_vec();
+        self.clone()
+    }
+
+    /// Rotate the polygon clockwise by the given degrees around the center of the box around it, or
+    /// around the point defined by [`Polygon::pivot`].
+    /// ## Example
+    /// ```
+    /// use image_builder::Polygon;
+    ///
+    /// Polygon::new(&[(50.0, 0.0), (100.0, 100.0), (0.0, 100.0)])
+    ///     .rotate(90.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the polygon is rotated.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polygon;
+    ///
+    /// Polygon::new(&[(50.0, 0.0), (100.0, 100.0), (0.0, 100.0)])
+    ///     .rotate(90.0)
+    ///     .pivot(0.0, 0.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the polygon and its stroke, from `0.0` (invisible) to `1.0` (opaque).
+    /// ## Example
+    /// ```
+    /// use image_builder::Polygon;
+    ///
+    /// Polygon::new(&[(50.0, 0.0), (100.0, 100.0), (0.0, 100.0)])
+    ///     .opacity(0.5);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the colors of the polygon are mixed with what is beneath it. Refer to the
+    /// [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{BlendMode, Polygon};
+    ///
+    /// Polygon::new(&[(50.0, 0.0), (100.0, 100.0), (0.0, 100.0)])
+    ///     .blend_mode(BlendMode::HardLight);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the polygon and its stroke, moved by the offset in pixels and softened
+    /// by the blur radius.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polygon;
+    ///
+    /// Polygon::new(&[(50.0, 0.0), (100.0, 100.0), (0.0, 100.0)])
+    ///     .shadow(4, 4, 10, [0, 0, 0, 100]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
+}
+
+#[derive(Clone)]
+pub struct PolygonValues<'a> {
+    pub points: &'a [(f32, f32)],
+    pub fill: Option<&'a Fill>,
+    pub stroke: Option<StrokeValues<'a>>,
+    pub effects: Effects,
+}
+pub fn extract(polygon: &Polygon) -> PolygonValues<'_> {
+    PolygonValues {
+        points: &polygon.points,
+        fill: polygon.fill.as_ref(),
+        stroke: polygon.stroke.map(|(width, color)| StrokeValues {
+            width: width as f32,
+            color: Rgba(color),
+            cap: LineCap::Butt,
+            join: polygon.join,
+            dash: &polygon.dash,
+        }),
+        effects: polygon.effects,
+    }
+}
@@ -0,0 +1,196 @@
+use image::Rgba;
+
+use crate::{
+    blend::BlendMode,
+    colors::{self, Color},
+    effects::Effects,
+    line::LineValues,
+    stroke::{LineCap, LineJoin, StrokeValues},
+};
+
+/// Specifications of an open line made of connected segments.
+#[derive(Clone)]
+pub struct Polyline {
+    points: Vec<(f32, f32)>,
+    width: u32,
+    color: Color,
+    cap: LineCap,
+    join: LineJoin,
+    dash: Vec<u32>,
+    effects: Effects,
+}
+impl Polyline {
+    /// This method instantiates a specifications of a line that goes through each point in order.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polyline;
+    ///
+    /// Polyline::new(&[(10.0, 100.0), (60.0, 20.0), (110.0, 100.0)]);
+    /// ```
+    pub fn new(points: &[(f32, f32)]) -> Polyline {
+        Polyline {
+            points: points.to_vec(),
+            width: 1,
+            color: colors::BLACK,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            dash: Vec::new(),
+            effects: Effects::default(),
+        }
+    }
+
+    /// Define the width of the line in pixels.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polyline;
+    ///
+    /// Polyline::new(&[(10.0, 100.0), (60.0, 20.0), (110.0, 100.0)])
+    ///     .width(4);
+    /// ```
+    pub fn width(&mut self, width: u32) -> Self {
+        self.width = width;
+        self.clone()
+    }
+
+    /// Define the color of the line.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Polyline, colors};
+    ///
+    /// Polyline::new(&[(10.0, 100.0), (60.0, 20.0), (110.0, 100.0)])
+    ///     .color(colors::RED);
+    /// ```
+    pub fn color(&mut self, color: Color) -> Self {
+        self.color = color;
+        self.clone()
+    }
+
+    /// Define the shape of the ends of the line. The default value is [`LineCap::Butt`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{LineCap, Polyline};
+    ///
+    /// Polyline::new(&[(10.0, 100.0), (60.0, 20.0), (110.0, 100.0)])
+    ///     .width(10)
+    ///     .cap(LineCap::Square);
+    /// ```
+    pub fn cap(&mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self.clone()
+    }
+
+    /// Define the shape of the corners where two segments meet. The default value is
+    /// [`LineJoin::Miter`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{LineJoin, Polyline};
+    ///
+    /// Polyline::new(&[(10.0, 100.0), (60.0, 20.0), (110.0, 100.0)])
+    ///     .width(10)
+    ///     .join(LineJoin::Round);
+    /// ```
+    pub fn join(&mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self.clone()
+    }
+
+    /// Draw the line as dashes, alternating the lengths in pixels of the visible and the hidden
+    /// parts. A pattern with an odd number of lengths is repeated to get an even number.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polyline;
+    ///
+    /// Polyline::new(&[(10.0, 100.0), (60.0, 20.0), (110.0, 100.0)])
+    ///     .dash(&[8, 4]);
+    /// ```
+    pub fn dash(&mut self, pattern: &[u32]) -> Self {
+        self.dash = pattern.to_vec();
+        self.clone()
+    }
+
+    /// Rotate the polyline clockwise by the given degrees around the center of the box around it, or
+    /// around the point defined by [`Polyline::pivot`].
+    /// ## Example
+    /// ```
+    /// use image_builder::Polyline;
+    ///
+    /// Polyline::new(&[(10.0, 90.0), (50.0, 10.0), (90.0, 90.0)])
+    ///     .rotate(180.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the polyline is rotated.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polyline;
+    ///
+    /// Polyline::new(&[(10.0, 90.0), (50.0, 10.0), (90.0, 90.0)])
+    ///     .rotate(20.0)
+    ///     .pivot(10.0, 90.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the polyline, from `0.0` (invisible) to `1.0` (opaque). Unlike a
+    /// translucent color, it is applied to the whole line at once, so the joins do not look darker.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polyline;
+    ///
+    /// Polyline::new(&[(10.0, 90.0), (50.0, 10.0), (90.0, 90.0)])
+    ///     .width(6)
+    ///     .opacity(0.5);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the color of the polyline is mixed with what is beneath it. Refer to the
+    /// [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{BlendMode, Polyline};
+    ///
+    /// Polyline::new(&[(10.0, 90.0), (50.0, 10.0), (90.0, 90.0)])
+    ///     .blend_mode(BlendMode::Multiply);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the polyline, moved by the offset in pixels and softened by the blur
+    /// radius.
+    /// ## Example
+    /// ```
+    /// use image_builder::Polyline;
+    ///
+    /// Polyline::new(&[(10.0, 90.0), (50.0, 10.0), (90.0, 90.0)])
+    ///     .width(4)
+    ///     .shadow(2, 2, 4, [0, 0, 0, 80]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
+}
+
+pub fn extract(polyline: &Polyline) -> LineValues<'_> {
+    LineValues {
+        points: polyline.points.clone(),
+        stroke: StrokeValues {
+            width: polyline.width as f32,
+            color: Rgba(polyline.color),
+            cap: polyline.cap,
+            join: polyline.join,
+            dash: &polyline.dash,
+        },
+        effects: polyline.effects,
+    }
+}
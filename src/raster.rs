@@ -0,0 +1,408 @@
+use std::f32::consts::PI;
+
+use image::{Pixel, Rgba, RgbaImage};
+use rusttype::{point, Font, OutlineBuilder, Scale};
+
+use crate::{
+    fill::{self, Paint},
+    path,
+};
+
+/// Number of scanlines sampled inside each row of pixels, which defines the quality of the
+/// anti-aliasing on the vertical axis. On the horizontal axis, the coverage is exact.
+const SUBSAMPLES: usize = 16;
+
+/// Maximum distance, in pixels, between a curve and the segments used to approximate it.
+pub const TOLERANCE: f32 = 0.1;
+
+/// Maximum number of segments used to approximate a curve, reached by huge radii or control
+/// points whose segments would otherwise be too short to count.
+pub const MAX_SEGMENTS: usize = 1024;
+
+pub type Point = (f32, f32);
+
+/// Rule used to decide which areas are inside of a shape made of overlapping contours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+/// Coverage of each pixel of an area, from `0.0` (outside of the shape) to `1.0` (inside of it).
+pub struct Coverage {
+    pub width: u32,
+    pub height: u32,
+    data: Vec<f32>,
+}
+impl Coverage {
+    pub fn new(width: u32, height: u32) -> Coverage {
+        Coverage {
+            width,
+            height,
+            data: vec![0.0; width as usize * height as usize],
+        }
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        self.data[(y * self.width + x) as usize]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, coverage: f32) {
+        self.data[(y * self.width + x) as usize] = coverage;
+    }
+}
+
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    winding: i32,
+}
+
+/// Adds the horizontal coverage of the span between `x0` and `x1` to a row of pixels.
+fn add_span(row: &mut [f32], x0: f32, x1: f32, weight: f32) {
+    let width = row.len() as f32;
+    let (x0, x1) = (x0.clamp(0.0, width), x1.clamp(0.0, width));
+    if x1 <= x0 {
+        return;
+    }
+
+    let (first, last) = (x0.floor() as usize, x1.floor() as usize);
+    if first == last {
+        row[first] += (x1 - x0) * weight;
+        return;
+    }
+    row[first] += (first as f32 + 1.0 - x0) * weight;
+    for coverage in row.iter_mut().take(last).skip(first + 1) {
+        *coverage += weight;
+    }
+    if last < row.len() {
+        row[last] += (x1 - last as f32) * weight;
+    }
+}
+
+/// Rasterizes closed polygons into an anti-aliased mask of the given size.
+pub fn fill(polygons: &[Vec<Point>], rule: FillRule, width: u32, height: u32) -> Coverage {
+    let mut edges = Vec::new();
+    for polygon in polygons {
+        for (index, &a) in polygon.iter().enumerate() {
+            let b = polygon[(index + 1) % polygon.len()];
+            if a.1 < b.1 {
+                edges.push(Edge {
+                    x0: a.0,
+                    y0: a.1,
+                    x1: b.0,
+                    y1: b.1,
+                    winding: 1,
+                });
+            } else if a.1 > b.1 {
+                edges.push(Edge {
+                    x0: b.0,
+                    y0: b.1,
+                    x1: a.0,
+                    y1: a.1,
+                    winding: -1,
+                });
+            }
+        }
+    }
+
+    let mut mask = Coverage::new(width, height);
+    let mut row = vec![0.0; width as usize];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    let weight = 1.0 / SUBSAMPLES as f32;
+    for y in 0..height {
+        let top = y as f32;
+        let active: Vec<&Edge> = edges
+            .iter()
+            .filter(|edge| edge.y1 > top && edge.y0 < top + 1.0)
+            .collect();
+        if active.is_empty() {
+            continue;
+        }
+
+        for sample in 0..SUBSAMPLES {
+            let sample_y = top + (sample as f32 + 0.5) * weight;
+            crossings.clear();
+            for edge in active.iter() {
+                if sample_y >= edge.y0 && sample_y < edge.y1 {
+                    let t = (sample_y - edge.y0) / (edge.y1 - edge.y0);
+                    crossings.push((edge.x0 + t * (edge.x1 - edge.x0), edge.winding));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for index in 0..crossings.len().saturating_sub(1) {
+                winding += crossings[index].1;
+                let inside = match rule {
+                    FillRule::NonZero => winding != 0,
+                    FillRule::EvenOdd => winding % 2 != 0,
+                };
+                if inside {
+                    add_span(&mut row, crossings[index].0, crossings[index + 1].0, weight);
+                }
+            }
+        }
+
+        let start = (y * width) as usize;
+        for (target, coverage) in mask.data[start..start + width as usize]
+            .iter_mut()
+            .zip(row.iter_mut())
+        {
+            *target = coverage.min(1.0);
+            *coverage = 0.0;
+        }
+    }
+    mask
+}
+
+/// Appends to the polygon the points of an elliptical arc, starting at the angle `start` and
+/// sweeping `sweep` radians.
+pub fn arc(points: &mut Vec<Point>, center: Point, radius: Point, start: f32, sweep: f32) {
+    let largest = radius.0.max(radius.1);
+    if largest <= 0.0 {
+        points.push(center);
+        return;
+    }
+    let step = 2.0 * (1.0 - TOLERANCE / largest).max(-1.0).acos();
+    let segments = ((sweep.abs() / step).ceil() as usize).clamp(1, MAX_SEGMENTS);
+    for segment in 0..=segments {
+        let angle = start + sweep * segment as f32 / segments as f32;
+        points.push((
+            center.0 + radius.0 * angle.cos(),
+            center.1 + radius.1 * angle.sin(),
+        ));
+    }
+}
+
+/// Polygon of an ellipse.
+pub fn ellipse(center: Point, radii: Point) -> Vec<Point> {
+    let mut points = Vec::new();
+    arc(&mut points, center, radii, 0.0, 2.0 * PI);
+    points
+}
+
+/// Polygon of a rectangle with rounded corners. The radii are given in the order top left, top
+/// right, bottom right and bottom left, and are reduced when they do not fit in the rectangle.
+pub fn rounded_rect(x: f32, y: f32, width: f32, height: f32, radii: [f32; 4]) -> Vec<Point> {
+    let limit = (width.min(height) / 2.0).max(0.0);
+    let [top_left, top_right, bottom_right, bottom_left] = radii.map(|r| r.clamp(0.0, limit));
+
+    let mut points = Vec::new();
+    let (right, bottom) = (x + width, y + height);
+    arc(
+        &mut points,
+        (x + top_left, y + top_left),
+        (top_left, top_left),
+        PI,
+        PI / 2.0,
+    );
+    arc(
+        &mut points,
+        (right - top_right, y + top_right),
+        (top_right, top_right),
+        -PI / 2.0,
+        PI / 2.0,
+    );
+    arc(
+        &mut points,
+        (right - bottom_right, bottom - bottom_right),
+        (bottom_right, bottom_right),
+        0.0,
+        PI / 2.0,
+    );
+    arc(
+        &mut points,
+        (x + bottom_left, bottom - bottom_left),
+        (bottom_left, bottom_left),
+        PI / 2.0,
+        PI / 2.0,
+    );
+    points
+}
+
+/// Draws over the layer the color returned for each pixel, using the mask as the opacity of the
+/// pixels.
+pub fn paint<F>(layer: &mut RgbaImage, mask: &Coverage, color_at: F)
+where
+    F: Fn(u32, u32) -> Rgba<u8>,
+{
+    for (x, y, pixel) in layer.enumerate_pixels_mut() {
+        let coverage = mask.get(x, y);
+        if coverage > 0.0 {
+            let mut color = color_at(x, y);
+            color.0[3] = (color.0[3] as f32 * coverage).round() as u8;
+            pixel.blend(&color);
+        }
+    }
+}
+
+/// Adds to the mask the coverage of the glyphs of a single line of text, drawn the same way as
+/// `imageproc::drawing::draw_text_mut` does, with the top of the line at the given point.
+pub fn glyphs(mask: &mut Coverage, font: &Font, scale: Scale, content: &str, x: i32, y: i32) {
+    let ascent = font.v_metrics(scale).ascent;
+    for glyph in font.layout(content, scale, point(0.0, ascent)) {
+        if let Some(bounds) = glyph.pixel_bounding_box() {
+            glyph.draw(|gx, gy, coverage| {
+                let mask_x = x + bounds.min.x + gx as i32;
+                let mask_y = y + bounds.min.y + gy as i32;
+                if (0..mask.width as i32).contains(&mask_x)
+                    && (0..mask.height as i32).contains(&mask_y)
+                {
+                    let index = (mask_y as u32 * mask.width + mask_x as u32) as usize;
+                    mask.data[index] = mask.data[index].max(coverage);
+                }
+            });
+        }
+    }
+}
+
+/// Collects the outline of a glyph as contours of straight segments, moved by an offset.
+struct GlyphOutline {
+    contours: Vec<Vec<Point>>,
+    current: Vec<Point>,
+    offset: Point,
+}
+impl GlyphOutline {
+    fn point(&self, x: f32, y: f32) -> Point {
+        (x + self.offset.0, y + self.offset.1)
+    }
+
+    fn last(&self) -> Point {
+        self.current.last().copied().unwrap_or(self.offset)
+    }
+}
+impl OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.current.push(self.point(x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.current.push(to);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (from, control, to) = (self.last(), self.point(x1, y1), self.point(x, y));
+        path::quad_points(&mut self.current, from, control, to);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (from, c1, c2, to) = (
+            self.last(),
+            self.point(x1, y1),
+            self.point(x2, y2),
+            self.point(x, y),
+        );
+        path::cubic_points(&mut self.current, from, c1, c2, to);
+    }
+
+    fn close(&mut self) {
+        if self.current.len() > 1 {
+            self.contours.push(std::mem::take(&mut self.current));
+        }
+        self.current.clear();
+    }
+}
+
+/// Contours of the glyphs of a single line of text, placed the same way as by [`glyphs`].
+pub fn glyph_contours(font: &Font, scale: Scale, content: &str, x: i32, y: i32) -> Vec<Vec<Point>> {
+    let ascent = font.v_metrics(scale).ascent;
+    let mut contours = Vec::new();
+    for glyph in font.layout(content, scale, point(0.0, ascent)) {
+        if let Some(bounds) = glyph.pixel_bounding_box() {
+            let mut outline = GlyphOutline {
+                contours: Vec::new(),
+                current: Vec::new(),
+                offset: ((x + bounds.min.x) as f32, (y + bounds.min.y) as f32),
+            };
+            glyph.build_outline(&mut outline);
+            outline.close();
+            contours.extend(outline.contours);
+        }
+    }
+    contours
+}
+
+/// Smallest box, in whole pixels, that contains all the points of the contours, as the top left
+/// corner and the bottom right corner.
+fn bounds(contours: &[Vec<Point>]) -> Option<((f32, f32), (f32, f32))> {
+    let mut points = contours.iter().flatten();
+    let first = *points.next()?;
+    let (min, max) = points.fold((first, first), |(min, max), point| {
+        (
+            (min.0.min(point.0), min.1.min(point.1)),
+            (max.0.max(point.0), max.1.max(point.1)),
+        )
+    });
+    Some(((min.0.floor(), min.1.floor()), (max.0.ceil(), max.1.ceil())))
+}
+
+fn translate(contours: &[Vec<Point>], offset: Point) -> Vec<Vec<Point>> {
+    contours
+        .iter()
+        .map(|contour| {
+            contour
+                .iter()
+                .map(|point| (point.0 - offset.0, point.1 - offset.1))
+                .collect()
+        })
+        .collect()
+}
+
+/// Draws a shape whose contours are in the coordinates of the image into a layer that covers only
+/// the area of the shape, returning the layer and its position. The fill is drawn first, and the
+/// positions of its gradients are relative to the box around the filled contours. The stroke, which
+/// is made of pieces filled with the non-zero rule, is drawn over it. The layer is also limited to
+/// the area that `reach` returns for the center of the shape, outside of which nothing would show.
+pub fn shape(
+    fill: Option<(&[Vec<Point>], FillRule, &Paint)>,
+    stroke: Option<(&[Vec<Point>], Rgba<u8>)>,
+    reach: impl FnOnce(Point) -> (Point, Point),
+) -> Option<(RgbaImage, i64, i64)> {
+    let mut contours = Vec::new();
+    if let Some((fill_contours, _, _)) = fill {
+        contours.extend_from_slice(fill_contours);
+    }
+    if let Some((stroke_pieces, _)) = stroke {
+        contours.extend_from_slice(stroke_pieces);
+    }
+    let (min, max) = bounds(&contours)?;
+    let center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+    let (reach_min, reach_max) = reach(center);
+    let min = (
+        min.0.max(reach_min.0.floor()),
+        min.1.max(reach_min.1.floor()),
+    );
+    let max = (max.0.min(reach_max.0.ceil()), max.1.min(reach_max.1.ceil()));
+    if max.0 <= min.0 || max.1 <= min.1 {
+        return None;
+    }
+    let (width, height) = ((max.0 - min.0) as u32, (max.1 - min.1) as u32);
+
+    let mut layer = RgbaImage::new(width, height);
+    if let Some((fill_contours, rule, paint_fill)) = fill {
+        if let Some((fill_min, fill_max)) = bounds(fill_contours) {
+            let mask = self::fill(&translate(fill_contours, min), rule, width, height);
+            let size = (fill_max.0 - fill_min.0, fill_max.1 - fill_min.1);
+            let origin = (fill_min.0 - min.0, fill_min.1 - min.1);
+            paint(&mut layer, &mask, |x, y| {
+                let point = (x as f32 - origin.0 + 0.5, y as f32 - origin.1 + 0.5);
+                fill::color_at(paint_fill, point, size)
+            });
+        }
+    }
+    if let Some((stroke_pieces, color)) = stroke {
+        let mask = self::fill(
+            &translate(stroke_pieces, min),
+            FillRule::NonZero,
+            width,
+            height,
+        );
+        paint(&mut layer, &mask, |_, _| color);
+    }
+    Some((layer, min.0 as i64, min.1 as i64))
+}
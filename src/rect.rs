@@ -1,13 +1,34 @@
 use image::Rgba;
 
-use crate::colors::{self, Color};
+use crate::{
+    blend::BlendMode,
+    colors::{self, Color},
+    effects::Effects,
+    fill::Fill,
+};
+
+/// Where the border of a rect is drawn relative to its edges. See [`Rect::border_position`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorderPosition {
+    /// The border is drawn inside the rect, covering its edges.
+    Inner,
+    /// The border is centered on the edges of the rect.
+    Center,
+    /// The border is drawn around the rect, making it bigger.
+    Outer,
+}
 
 /// Specifications of a rectangular shape.
 #[derive(Clone)]
 pub struct Rect {
     position: (u32, u32),
     size: (u32, u32),
-    color: Color,
+    fill: Fill,
+    corner_radii: [u32; 4],
+    border: Option<(u32, Color)>,
+    border_position: BorderPosition,
+    backdrop_blur: Option<f32>,
+    effects: Effects,
 }
 impl Rect {
     /// This method instantiates a specifications of a rectangular shape.
@@ -21,7 +42,12 @@ impl Rect {
         Rect {
             position: (0, 0),
             size: (10, 10),
-            color: colors::GREEN,
+            fill: Fill::Solid(colors::GREEN),
+            corner_radii: [0, 0, 0, 0],
+            border: None,
+            border_position: BorderPosition::Inner,
+            backdrop_blur: None,
+            effects: Effects::default(),
         }
     }
 
@@ -66,25 +92,245 @@ impl Rect {
     ///     .color([150, 30, 255, 150]); // rgba values
     /// ```
     pub fn color(&mut self, color: Color) -> Self {
-        self.color = color;
+        self.fill = Fill::Solid(color);
+        self.clone()
+    }
+
+    /// Fill the rect with a solid color or a gradient. The positions of a gradient are relative to
+    /// the top left corner of the rect. Refer to the [`Fill`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Fill, Rect};
+    ///
+    /// Rect::new()
+    ///     .size(300, 100)
+    ///     .fill(Fill::LinearGradient {
+    ///         angle: 90.0,
+    ///         stops: vec![(0.0, colors::BLUE), (0.5, colors::GREEN), (1.0, colors::YELLOW)],
+    ///     });
+    /// ```
+    pub fn fill(&mut self, fill: Fill) -> Self {
+        self.fill = fill;
+        self.clone()
+    }
+
+    /// Round all the corners of the rect with the same radius in pixels. Radii bigger than half
+    /// of the smallest side of the rect are reduced to fit.
+    /// ## Example
+    /// ```
+    /// use image_builder::Rect;
+    ///
+    /// Rect::new()
+    ///     .size(150, 50)
+    ///     .corner_radius(10);
+    /// ```
+    pub fn corner_radius(&mut self, radius: u32) -> Self {
+        self.corner_radii = [radius; 4];
+        self.clone()
+    }
+
+    /// Round each corner of the rect with a different radius in pixels.
+    /// ## Example
+    /// ```
+    /// use image_builder::Rect;
+    ///
+    /// Rect::new()
+    ///     .size(150, 50)
+    ///     .corner_radii(20, 20, 0, 0); // top left, top right, bottom right, bottom left
+    /// ```
+    pub fn corner_radii(
+        &mut self,
+        top_left: u32,
+        top_right: u32,
+        bottom_right: u32,
+        bottom_left: u32,
+    ) -> Self {
+        self.corner_radii = [top_left, top_right, bottom_right, bottom_left];
+        self.clone()
+    }
+
+    /// Draw a border with the given width in pixels and color, following the rounded corners of
+    /// the rect.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Rect, colors};
+    ///
+    /// Rect::new()
+    ///     .size(150, 50)
+    ///     .color(colors::WHITE)
+    ///     .border(4, colors::BLUE);
+    /// ```
+    pub fn border(&mut self, width: u32, color: Color) -> Self {
+        self.border = Some((width, color));
+        self.clone()
+    }
+
+    /// Define where the border is drawn relative to the edges of the rect. The default value is
+    /// [`BorderPosition::Inner`], which keeps the rect inside of its size.
+    /// ## Example
+    /// ```
+    /// use image_builder::{BorderPosition, Rect, colors};
+    ///
+    /// Rect::new()
+    ///     .size(150, 50)
+    ///     .border(4, colors::BLUE)
+    ///     .border_position(BorderPosition::Outer);
+    /// ```
+    pub fn border_position(&mut self, position: BorderPosition) -> Self {
+        self.border_position = position;
+        self.clone()
+    }
+
+    /// Blur what was already drawn beneath the rect with a gaussian blur of the given standard
+    /// deviation in pixels, inside of the rounded corners of the rect. Combined with a translucent
+    /// fill, it creates a frosted glass panel.
+    /// ## Example
+    /// ```
+    /// use image_builder::Rect;
+    ///
+    /// Rect::new()
+    ///     .size(300, 120)
+    ///     .corner_radius(16)
+    ///     .color([255, 255, 255, 80])
+    ///     .backdrop_blur(10.0);
+    /// ```
+    pub fn backdrop_blur(&mut self, sigma: f32) -> Self {
+        self.backdrop_blur = Some(sigma);
+        self.clone()
+    }
+
+    /// Rotate the rect clockwise by the given degrees around its center, or around the point defined
+    /// by [`Rect::pivot`]. The border and the rounded corners are rotated with it.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Rect};
+    ///
+    /// Rect::new() // A diagonal ribbon.
+    ///     .size(300, 40)
+    ///     .position(300, 40)
+    ///     .color(colors::RED)
+    ///     .rotate(45.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the rect is rotated.
+    /// ## Example
+    /// ```
+    /// use image_builder::Rect;
+    ///
+    /// Rect::new()
+    ///     .size(100, 20)
+    ///     .position(50, 50)
+    ///     .rotate(90.0)
+    ///     .pivot(50.0, 50.0);
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the rect and its border, from `0.0` (invisible) to `1.0` (opaque). Unlike
+    /// a translucent color, the border does not show the body of the rect through it.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Rect};
+    ///
+    /// Rect::new() // A dark overlay behind a caption.
+    ///     .size(400, 80)
+    ///     .color(colors::BLACK)
+    ///     .opacity(0.6);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
         self.clone()
     }
+
+    /// Define how the colors of the rect are mixed with what is beneath it, such as a color wash over
+    /// a photo. Refer to the [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, BlendMode, Rect};
+    ///
+    /// Rect::new()
+    ///     .size(400, 300)
+    ///     .color(colors::PURPLE)
+    ///     .blend_mode(BlendMode::Color);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the rect, moved by the offset in pixels and softened by the blur radius,
+    /// such as the elevation of a card.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Rect};
+    ///
+    /// Rect::new()
+    ///     .size(200, 120)
+    ///     .color(colors::WHITE)
+    ///     .corner_radius(12)
+    ///     .shadow(0, 4, 16, [0, 0, 0, 60]);
+    /// ```
+    ///
+    /// Blur radii above 100 pixels are drawn as 100 pixels, which keeps huge radii cheap.
+    /// ```
+    /// use image_builder::{colors, Image, Rect};
+    ///
+    /// let mut image = Image::new(40, 40, colors::WHITE);
+    /// image.add_rect(
+    ///     Rect::new()
+    ///         .position(10, 10)
+    ///         .size(20, 20)
+    ///         .color(colors::RED)
+    ///         .shadow(0, 0, u32::MAX, colors::BLACK),
+    /// );
+    /// let buffer = image.render().unwrap();
+    /// assert_eq!(buffer.get_pixel(20, 20).0, colors::RED);
+    /// assert!(buffer.get_pixel(5, 5).0[0] < 255);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
+        self.clone()
+    }
+}
+
+impl Default for Rect {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Clone)]
-pub struct RectValues {
+pub struct RectValues<'a> {
     pub x: i32,
     pub y: i32,
     pub width: u32,
     pub height: u32,
-    pub color: Rgba<u8>,
+    pub fill: &'a Fill,
+    pub corner_radii: [f32; 4],
+    pub border: Option<(f32, Rgba<u8>)>,
+    pub border_position: BorderPosition,
+    pub backdrop_blur: Option<f32>,
+    pub effects: Effects,
 }
-pub fn extract(rect: &Rect) -> RectValues {
+pub fn extract(rect: &Rect) -> RectValues<'_> {
     RectValues {
         x: rect.position.0 as i32,
         y: rect.position.1 as i32,
         width: rect.size.0,
         height: rect.size.1,
-        color: Rgba(rect.color),
+        fill: &rect.fill,
+        corner_radii: rect.corner_radii.map(|radius| radius as f32),
+        border: rect
+            .border
+            .map(|(width, color)| (width as f32, Rgba(color))),
+        border_position: rect.border_position,
+        backdrop_blur: rect.backdrop_blur,
+        effects: rect.effects,
     }
 }
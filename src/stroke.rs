@@ -0,0 +1,231 @@
+use image::Rgba;
+
+use crate::raster::{self, Point};
+
+/// Longest miter allowed, relative to half the width of the line, before a miter join is drawn as
+/// a bevel join.
+const MITER_LIMIT: f32 = 4.0;
+
+/// Shape drawn at the ends of open lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineCap {
+    /// The line ends exactly at its end points.
+    Butt,
+    /// The line ends with a half circle around its end points.
+    Round,
+    /// The line ends with a half square around its end points.
+    Square,
+}
+
+/// Shape drawn where two segments of a line meet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    /// The outer edges of the segments are extended until they meet, sharp corners that would be
+    /// too long are drawn as bevels.
+    Miter,
+    /// The corner is rounded.
+    Round,
+    /// The corner is cut off.
+    Bevel,
+}
+
+#[derive(Clone)]
+pub struct StrokeValues<'a> {
+    pub width: f32,
+    pub color: Rgba<u8>,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub dash: &'a [u32],
+}
+
+fn signed_area(polygon: &[Point]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Makes every piece of a stroke turn in the same direction, so that filling them together with
+/// the non-zero rule results in their union.
+fn push_piece(pieces: &mut Vec<Vec<Point>>, mut piece: Vec<Point>) {
+    if signed_area(&piece) < 0.0 {
+        piece.reverse();
+    }
+    pieces.push(piece);
+}
+
+fn direction(a: Point, b: Point) -> Point {
+    let length = (b.0 - a.0).hypot(b.1 - a.1);
+    ((b.0 - a.0) / length, (b.1 - a.1) / length)
+}
+
+/// Splits a line into the visible parts of the dash pattern. The pattern continues from one
+/// segment to the next.
+fn dashes(points: &[Point], pattern: &[u32]) -> Vec<Vec<Point>> {
+    let mut pattern: Vec<f32> = pattern.iter().map(|&length| length as f32).collect();
+    if pattern.len() % 2 == 1 {
+        pattern.extend(pattern.clone());
+    }
+
+    let mut dashes = Vec::new();
+    let mut current = vec![points[0]];
+    let (mut index, mut remaining) = (0, pattern[0]);
+    for segment in points.windows(2) {
+        let (a, b) = (segment[0], segment[1]);
+        let length = (b.0 - a.0).hypot(b.1 - a.1);
+        let mut travelled = 0.0;
+        while length - travelled > remaining {
+            travelled += remaining;
+            let t = travelled / length;
+            let point = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+            if index % 2 == 0 {
+                current.push(point);
+                dashes.push(std::mem::take(&mut current));
+            } else {
+                current = vec![point];
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+        remaining -= length - travelled;
+        if index % 2 == 0 {
+            current.push(b);
+        }
+    }
+    if index % 2 == 0 && current.len() > 1 {
+        dashes.push(current);
+    }
+    dashes
+}
+
+fn join(
+    pieces: &mut Vec<Vec<Point>>,
+    vertex: Point,
+    before: Point,
+    after: Point,
+    s: &StrokeValues,
+) {
+    let half = s.width / 2.0;
+    let (d1, d2) = (direction(before, vertex), direction(vertex, after));
+    let (n1, n2) = ((-d1.1, d1.0), (-d2.1, d2.0));
+    let side = if d1.0 * d2.1 - d1.1 * d2.0 > 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    let end = (vertex.0 + side * n1.0 * half, vertex.1 + side * n1.1 * half);
+    let start = (vertex.0 + side * n2.0 * half, vertex.1 + side * n2.1 * half);
+
+    match s.join {
+        LineJoin::Round => push_piece(pieces, raster::ellipse(vertex, (half, half))),
+        LineJoin::Miter | LineJoin::Bevel => {
+            let cosine = 1.0 + n1.0 * n2.0 + n1.1 * n2.1;
+            let miter_length = if cosine > 0.0 {
+                2.0 / (2.0 * cosine).sqrt()
+            } else {
+                f32::INFINITY
+            };
+            if s.join == LineJoin::Miter && miter_length <= MITER_LIMIT {
+                let tip = (
+                    vertex.0 + side * (n1.0 + n2.0) * half / cosine,
+                    vertex.1 + side * (n1.1 + n2.1) * half / cosine,
+                );
+                push_piece(pieces, vec![vertex, end, tip, start]);
+            } else {
+                push_piece(pieces, vec![vertex, end, start]);
+            }
+        }
+    }
+}
+
+fn cap(pieces: &mut Vec<Vec<Point>>, end: Point, outwards: Point, s: &StrokeValues) {
+    let half = s.width / 2.0;
+    let normal = (-outwards.1, outwards.0);
+    match s.cap {
+        LineCap::Butt => {}
+        LineCap::Round => push_piece(pieces, raster::ellipse(end, (half, half))),
+        LineCap::Square => {
+            let far = (end.0 + outwards.0 * half, end.1 + outwards.1 * half);
+            push_piece(
+                pieces,
+                vec![
+                    (end.0 + normal.0 * half, end.1 + normal.1 * half),
+                    (far.0 + normal.0 * half, far.1 + normal.1 * half),
+                    (far.0 - normal.0 * half, far.1 - normal.1 * half),
+                    (end.0 - normal.0 * half, end.1 - normal.1 * half),
+                ],
+            );
+        }
+    }
+}
+
+/// Pieces of the outline of a line with the given stroke: one quadrilateral for each segment,
+/// plus the joins between them and the caps at the ends of open lines. The pieces must be filled
+/// together with the non-zero rule.
+pub fn outline(points: &[Point], closed: bool, s: &StrokeValues) -> Vec<Vec<Point>> {
+    let mut points: Vec<Point> = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 2 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.is_empty() || s.width <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut pieces = Vec::new();
+    if s.dash.iter().any(|&length| length > 0) && points.len() > 1 {
+        if closed {
+            points.push(points[0]);
+        }
+        for dash in dashes(&points, s.dash) {
+            pieces.extend(outline(&dash, false, &StrokeValues { dash: &[], ..*s }));
+        }
+        return pieces;
+    }
+
+    if points.len() == 1 {
+        if s.cap != LineCap::Butt {
+            cap(&mut pieces, points[0], (1.0, 0.0), s);
+            cap(&mut pieces, points[0], (-1.0, 0.0), s);
+        }
+        return pieces;
+    }
+
+    let half = s.width / 2.0;
+    let count = points.len();
+    let segments = if closed { count } else { count - 1 };
+    for index in 0..segments {
+        let (a, b) = (points[index], points[(index + 1) % count]);
+        let d = direction(a, b);
+        let n = (-d.1 * half, d.0 * half);
+        push_piece(
+            &mut pieces,
+            vec![
+                (a.0 + n.0, a.1 + n.1),
+                (b.0 + n.0, b.1 + n.1),
+                (b.0 - n.0, b.1 - n.1),
+                (a.0 - n.0, a.1 - n.1),
+            ],
+        );
+    }
+
+    let vertices = if closed { 0..count } else { 1..count - 1 };
+    for index in vertices {
+        let before = points[(index + count - 1) % count];
+        let after = points[(index + 1) % count];
+        join(&mut pieces, points[index], before, after, s);
+    }
+
+    if !closed {
+        cap(&mut pieces, points[0], direction(points[1], points[0]), s);
+        cap(
+            &mut pieces,
+            points[count - 1],
+            direction(points[count - 2], points[count - 1]),
+            s,
+        );
+    }
+    pieces
+}
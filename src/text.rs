@@ -1,7 +1,57 @@
 use image::Rgba;
 use rusttype::Scale;
 
-use crate::colors::{self, Color};
+use crate::{
+    blend::BlendMode,
+    colors::{self, Color},
+    effects::Effects,
+    fill::Fill,
+};
+
+/// Horizontal alignment of the lines of a text. See [`Text::align`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Vertical alignment of a text. See [`Text::valign`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VAlign {
+    Top,
+    Middle,
+    /// The baseline of the first line is placed at the vertical position of the text. The box of a
+    /// text with a maximum height moves up with it.
+    Baseline,
+    Bottom,
+}
+
+/// What happens to a text that does not fit within its maximum width, maximum height or maximum
+/// number of lines. See [`Text::overflow`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// The lines that do not fit are not drawn, and the parts of the glyphs outside the box of the
+    /// text are cut off.
+    Clip,
+    /// Like `Clip`, but the last line drawn, as well as any line wider than the maximum width, is
+    /// shortened and ends with the given string, such as `"…"`.
+    Ellipsis(String),
+    /// The whole text is drawn, even outside of its box.
+    Visible,
+}
+
+/// Measurements of a text after it is laid out, as returned by [`crate::Image::measure_text`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextMetrics {
+    /// The size used to draw the text, which is the size chosen by [`Text::fit_within`] when it
+    /// is used.
+    pub size: u32,
+    pub width: i32,
+    pub height: i32,
+    /// The number of lines drawn.
+    pub lines: usize,
+}
 
 /// Content and formatting of a text.
 #[derive(Clone)]
@@ -10,7 +60,19 @@ pub struct Text {
     size: u32,
     position: (u32, u32),
     font_name: String,
-    color: Color,
+    fill: Fill,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    line_height: f32,
+    align: Align,
+    valign: VAlign,
+    fit: Option<(u32, u32)>,
+    min_size: u32,
+    max_size: Option<u32>,
+    max_lines: Option<u32>,
+    overflow: Overflow,
+    stroke: Option<(u32, Color)>,
+    effects: Effects,
 }
 impl Text {
     /// This method instantiates a specifications of a text.
@@ -28,7 +90,19 @@ impl Text {
             size: 14,
             position: (0, 0),
             font_name: String::from("default"),
-            color: colors::BLACK,
+            fill: Fill::Solid(colors::BLACK),
+            max_width: None,
+            max_height: None,
+            line_height: 1.2,
+            align: Align::Left,
+            valign: VAlign::Top,
+            fit: None,
+            min_size: 1,
+            max_size: None,
+            max_lines: None,
+            overflow: Overflow::Visible,
+            stroke: None,
+            effects: Effects::default(),
         }
     }
 
@@ -60,8 +134,8 @@ impl Text {
 
     /// This method is used to set the font of a text, but it's important to remember to import the
     /// font using the add_custom_font method of the [`crate::Image`] structure (refer to the documentation
-    /// for more details). Trying to use a font that hasn't been imported will result in an error
-    /// in the application. Make sure to import the font correctly to avoid panics.
+    /// for more details). Trying to use a font that hasn't been imported will make the rendering
+    /// return an [`crate::Error::UnknownFont`].
     /// ## Example
     /// ```
     /// use image_builder::Text;
@@ -89,19 +163,330 @@ impl Text {
     ///     .color([30, 90, 150, 255]); // rgba values
     /// ```
     pub fn color(&mut self, color: Color) -> Self {
-        self.color = color;
+        self.fill = Fill::Solid(color);
+        self.clone()
+    }
+
+    /// Fill the glyphs of the text with a solid color or a gradient. The positions of a gradient
+    /// are relative to the top left corner of the text. Refer to the [`Fill`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Fill, Text};
+    ///
+    /// Text::new("Any text here")
+    ///     .size(50)
+    ///     .fill(Fill::LinearGradient {
+    ///         angle: 180.0,
+    ///         stops: vec![(0.0, colors::ORANGE), (1.0, colors::RED)],
+    ///     });
+    /// ```
+    pub fn fill(&mut self, fill: Fill) -> Self {
+        self.fill = fill;
+        self.clone()
+    }
+
+    /// Define the maximum width of the text in pixels. Lines longer than this width are wrapped on
+    /// the whitespace between the words, and explicit line breaks (`\n`) are always respected.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("A long title that does not fit in a single line")
+    ///     .max_width(300);
+    /// ```
+    pub fn max_width(&mut self, width: u32) -> Self {
+        self.max_width = Some(width);
+        self.clone()
+    }
+
+    /// Define the maximum height of the text in pixels. Lines that would end below this height are
+    /// handled according to the [`Overflow`] of the text, by default they are not drawn.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("A long title that does not fit in a single line")
+    ///     .max_width(300)
+    ///     .max_height(100);
+    /// ```
+    pub fn max_height(&mut self, height: u32) -> Self {
+        self.max_height = Some(height);
+        self.clone()
+    }
+
+    /// Define the distance between the top of two consecutive lines as a multiple of the text
+    /// size. The default value is `1.2`.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("First line\nSecond line")
+    ///     .line_height(1.5);
+    /// ```
+    pub fn line_height(&mut self, line_height: f32) -> Self {
+        self.line_height = line_height;
+        self.clone()
+    }
+
+    /// Define the horizontal alignment of each line of the text. When a maximum width is defined,
+    /// the lines are aligned inside the box that starts at the position of the text, otherwise the
+    /// position is used as the anchor point: the left end, the center or the right end of the lines.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Align, Text};
+    ///
+    /// Text::new("Centered title")
+    ///     .position(250, 20)
+    ///     .align(Align::Center);
+    /// ```
+    pub fn align(&mut self, align: Align) -> Self {
+        self.align = align;
+        self.clone()
+    }
+
+    /// Define the vertical alignment of the text. When a maximum height is defined, the text is
+    /// aligned inside the box that starts at the position of the text, otherwise the position is
+    /// used as the anchor point: the top, the middle, the baseline of the first line or the bottom
+    /// of the text.
+    /// ## Example
+    /// ```
+    /// use image_builder::{Align, Text, VAlign};
+    ///
+    /// Text::new("Centered title")
+    ///     .position(250, 250)
+    ///     .align(Align::Center)
+    ///     .valign(VAlign::Middle);
+    /// ```
+    ///
+    /// ```
+    /// use image_builder::{colors, Image, Overflow, Text, VAlign};
+    ///
+    /// let mut image = Image::new(300, 100, colors::WHITE);
+    /// image.add_text(
+    ///     Text::new("Baseline")
+    ///         .size(30)
+    ///         .position(10, 60)
+    ///         .max_width(200)
+    ///         .max_height(40)
+    ///         .valign(VAlign::Baseline)
+    ///         .overflow(Overflow::Clip),
+    /// );
+    /// let buffer = image.render().unwrap();
+    /// assert!(buffer.pixels().any(|pixel| pixel.0 != colors::WHITE));
+    /// ```
+    pub fn valign(&mut self, valign: VAlign) -> Self {
+        self.valign = valign;
+        self.clone()
+    }
+
+    /// With this method, the size of the text is chosen when the image is rendered: the largest size
+    /// between [`Text::min_size`] and [`Text::max_size`] whose text, wrapped when a maximum width is
+    /// defined, fits within the given width and height. Use [`crate::Image::measure_text`] to know
+    /// the chosen size.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("A dynamic title")
+    ///     .max_width(400)
+    ///     .fit_within(400, 120)
+    ///     .min_size(12)
+    ///     .max_size(90);
+    /// ```
+    pub fn fit_within(&mut self, width: u32, height: u32) -> Self {
+        self.fit = Some((width, height));
+        self.clone()
+    }
+
+    /// Define the smallest size that [`Text::fit_within`] can choose. The default value is `1`.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("A dynamic title")
+    ///     .fit_within(400, 120)
+    ///     .min_size(12);
+    /// ```
+    pub fn min_size(&mut self, size: u32) -> Self {
+        self.min_size = size;
+        self.clone()
+    }
+
+    /// Define the largest size that [`Text::fit_within`] can choose. When it is not defined, the
+    /// size of the text is used.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("A dynamic title")
+    ///     .fit_within(400, 120)
+    ///     .max_size(90);
+    /// ```
+    pub fn max_size(&mut self, size: u32) -> Self {
+        self.max_size = Some(size);
+        self.clone()
+    }
+
+    /// Define the maximum number of lines of the text. The lines beyond this number are handled
+    /// according to the [`Overflow`] of the text.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("A long description that will be broken into many lines")
+    ///     .max_width(200)
+    ///     .max_lines(2);
+    /// ```
+    pub fn max_lines(&mut self, lines: u32) -> Self {
+        self.max_lines = Some(lines);
+        self.clone()
+    }
+
+    /// Define what happens to the text that does not fit within its maximum width, maximum height
+    /// or maximum number of lines. The default value is [`Overflow::Visible`].
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Image, Overflow, Text};
+    ///
+    /// let image = Image::new(500, 500, colors::WHITE);
+    /// let name = Text::new("Jefferson Carlos")
+    ///     .size(30)
+    ///     .max_width(160)
+    ///     .max_lines(1)
+    ///     .overflow(Overflow::Ellipsis(String::from("…")));
+    /// let metrics = image.measure_text(&name).unwrap();
+    /// assert_eq!(metrics.lines, 1);
+    /// assert!(metrics.width <= 160);
+    /// ```
+    ///
+    /// A clipped text is measured by the part that is drawn, inside of its box.
+    /// ```
+    /// use image_builder::{colors, Image, Overflow, Text};
+    ///
+    /// let image = Image::new(500, 500, colors::WHITE);
+    /// let word = Text::new("Supercalifragilistic").size(30).max_width(100);
+    /// assert!(image.measure_text(&word).unwrap().width > 100);
+    /// let clipped = word.clone().overflow(Overflow::Clip);
+    /// assert_eq!(image.measure_text(&clipped).unwrap().width, 100);
+    /// ```
+    pub fn overflow(&mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self.clone()
+    }
+
+    /// Draw an outline with the given width in pixels and color around the glyphs, beneath the fill,
+    /// which keeps the text readable over photos. The corners of the outline are rounded.
+    /// ## Example
+    /// ```
+    /// use image_builder::{colors, Text};
+    ///
+    /// Text::new("WHEN THE BUILD PASSES") // A meme caption.
+    ///     .size(48)
+    ///     .color(colors::WHITE)
+    ///     .stroke(3, colors::BLACK);
+    /// ```
+    pub fn stroke(&mut self, width: u32, color: Color) -> Self {
+        self.stroke = Some((width, color));
+        self.clone()
+    }
+
+    /// Rotate the text clockwise by the given degrees around the center of its block, or around
+    /// the point defined by [`Text::pivot`]. Negative degrees rotate it counterclockwise.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("Revenue") // A vertical axis label.
+    ///     .position(20, 200)
+    ///     .rotate(-90.0);
+    /// ```
+    pub fn rotate(&mut self, degrees: f32) -> Self {
+        self.effects.rotation = degrees;
+        self.clone()
+    }
+
+    /// Define the point of the image, in pixels, around which the text is rotated.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("Image Builder")
+    ///     .position(100, 100)
+    ///     .rotate(30.0)
+    ///     .pivot(100.0, 100.0); // Rotates around the top left corner of the text.
+    /// ```
+    pub fn pivot(&mut self, x: f32, y: f32) -> Self {
+        self.effects.pivot = Some((x, y));
+        self.clone()
+    }
+
+    /// Define the opacity of the text, from `0.0` (invisible) to `1.0` (opaque), applied on top of
+    /// the alpha of its fill.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("Watermark")
+    ///     .size(40)
+    ///     .opacity(0.3);
+    /// ```
+    pub fn opacity(&mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self.clone()
+    }
+
+    /// Define how the colors of the text are mixed with what is beneath it. Refer to the
+    /// [`BlendMode`] for more details.
+    /// ## Example
+    /// ```
+    /// use image_builder::{BlendMode, Text};
+    ///
+    /// Text::new("Overlay")
+    ///     .size(60)
+    ///     .color([255, 255, 255, 255])
+    ///     .blend_mode(BlendMode::Overlay);
+    /// ```
+    pub fn blend_mode(&mut self, mode: BlendMode) -> Self {
+        self.effects.blend_mode = mode;
+        self.clone()
+    }
+
+    /// Draw a shadow beneath the text, moved by the offset in pixels and softened by the blur radius.
+    /// A blur radius of `0` draws a hard shadow.
+    /// ## Example
+    /// ```
+    /// use image_builder::Text;
+    ///
+    /// Text::new("Image Builder")
+    ///     .size(90)
+    ///     .shadow(5, 5, 6, [0, 0, 0, 100]);
+    /// ```
+    pub fn shadow(&mut self, offset_x: i32, offset_y: i32, blur_radius: u32, color: Color) -> Self {
+        self.effects.shadow = Some((offset_x, offset_y, blur_radius, color));
         self.clone()
     }
 }
 
 #[derive(Clone)]
 pub struct TextValues<'a> {
-    pub color: Rgba<u8>,
+    pub fill: &'a Fill,
     pub x: i32,
     pub y: i32,
     pub font_name: &'a str,
     pub scale: Scale,
     pub content: &'a str,
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+    pub line_height: f32,
+    pub align: Align,
+    pub valign: VAlign,
+    pub fit: Option<(u32, u32)>,
+    pub min_size: u32,
+    pub max_size: u32,
+    pub max_lines: Option<usize>,
+    pub overflow: &'a Overflow,
+    pub stroke: Option<(f32, Rgba<u8>)>,
+    pub effects: Effects,
 }
 
 pub fn extract<'a>(text: &'a Text) -> TextValues<'a> {
@@ -110,11 +495,25 @@ pub fn extract<'a>(text: &'a Text) -> TextValues<'a> {
         y: text.size as f32,
     };
     TextValues {
-        color: Rgba(text.color),
+        fill: &text.fill,
         x: text.position.0 as i32,
         y: text.position.1 as i32,
         scale,
         font_name: text.font_name.as_str(),
         content: &text.content,
+        max_width: text.max_width,
+        max_height: text.max_height,
+        line_height: text.line_height,
+        align: text.align,
+        valign: text.valign,
+        fit: text.fit,
+        min_size: text.min_size,
+        max_size: text.max_size.unwrap_or(text.size),
+        max_lines: text.max_lines.map(|lines| lines as usize),
+        overflow: &text.overflow,
+        stroke: text
+            .stroke
+            .map(|(width, color)| (width as f32, Rgba(color))),
+        effects: text.effects,
     }
 }
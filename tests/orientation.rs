@@ -0,0 +1,241 @@
+//! EXIF orientation of pictures loaded from bytes, using small images built on the fly with an
+//! orientation tag in each of the containers that carry EXIF data.
+
+use std::io::Cursor;
+
+use image::{ImageOutputFormat, Rgba, RgbaImage};
+use image_builder::{Image, Picture};
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+/// Size of the pictures and position of their red marker for each orientation, from `1` to `8`,
+/// when the upright picture is 3x2 pixels with the marker in its top left corner.
+const UPRIGHT: [((u32, u32), (u32, u32)); 8] = [
+    ((3, 2), (0, 0)),
+    ((3, 2), (2, 0)),
+    ((3, 2), (2, 1)),
+    ((3, 2), (0, 1)),
+    ((2, 3), (0, 0)),
+    ((2, 3), (1, 0)),
+    ((2, 3), (1, 2)),
+    ((2, 3), (0, 2)),
+];
+
+/// TIFF data with a single directory holding the orientation tag.
+fn tiff(orientation: u16, big_endian: bool) -> Vec<u8> {
+    let u16_bytes = |value: u16| {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+    let u32_bytes = |value: u32| {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+    let mut data = Vec::new();
+    data.extend_from_slice(if big_endian { b"MM" } else { b"II" });
+    data.extend_from_slice(&u16_bytes(42));
+    data.extend_from_slice(&u32_bytes(8));
+    data.extend_from_slice(&u16_bytes(1));
+    // Tag, SHORT type, count of one and the value padded to four bytes.
+    data.extend_from_slice(&u16_bytes(0x0112));
+    data.extend_from_slice(&u16_bytes(3));
+    data.extend_from_slice(&u32_bytes(1));
+    data.extend_from_slice(&u16_bytes(orientation));
+    data.extend_from_slice(&[0, 0]);
+    data.extend_from_slice(&u32_bytes(0));
+    data
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// A blue picture with a red marker in its top left sixth, encoded in the format.
+fn encoded(width: u32, height: u32, format: ImageOutputFormat) -> Vec<u8> {
+    let picture = RgbaImage::from_fn(width, height, |x, y| {
+        if x < width / 3 && y < height / 2 {
+            RED
+        } else {
+            BLUE
+        }
+    });
+    let mut bytes = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgba8(picture)
+        .to_rgb8()
+        .write_to(&mut bytes, format)
+        .unwrap();
+    bytes.into_inner()
+}
+
+/// A PNG file with the TIFF data in an eXIf chunk right after the header chunk.
+fn png(tiff: &[u8]) -> Vec<u8> {
+    let mut bytes = encoded(3, 2, ImageOutputFormat::Png);
+    let mut chunk = (tiff.len() as u32).to_be_bytes().to_vec();
+    chunk.extend_from_slice(b"eXIf");
+    chunk.extend_from_slice(tiff);
+    chunk.extend_from_slice(&crc32(&chunk[4..]).to_be_bytes());
+    // The signature and the IHDR chunk take the first 33 bytes.
+    bytes.splice(33..33, chunk);
+    bytes
+}
+
+/// A JPEG file, with blocks large enough to survive the compression, with the TIFF data in an
+/// APP1 segment right after the start of image marker.
+fn jpeg(tiff: &[u8]) -> Vec<u8> {
+    let mut bytes = encoded(48, 32, ImageOutputFormat::Jpeg(100));
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&(2 + 6 + tiff.len() as u16).to_be_bytes());
+    segment.extend_from_slice(b"Exif\0\0");
+    segment.extend_from_slice(tiff);
+    bytes.splice(2..2, segment);
+    bytes
+}
+
+/// An extended WebP file holding a lossless 3x2 image of a single color, followed by an EXIF chunk.
+fn webp(tiff: &[u8]) -> Vec<u8> {
+    let chunk = |kind: &[u8], data: &[u8]| {
+        let mut chunk = kind.to_vec();
+        chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    };
+    // Header of 3x2 pixels without alpha, no transforms, no color cache, no meta prefix codes,
+    // then five prefix codes of a single symbol each, so that pixels take no bits.
+    let mut bits: Vec<(u32, u32)> = vec![(2, 14), (1, 14), (0, 1), (0, 3), (0, 1), (0, 1), (0, 1)];
+    for symbol in [0, 0, 255, 255, 0] {
+        bits.extend_from_slice(&[(1, 1), (0, 1), (1, 1), (symbol, 8)]);
+    }
+    let mut lossless = vec![0x2F];
+    let (mut value, mut length) = (0_u64, 0);
+    for (bit_value, bit_length) in bits {
+        value |= (bit_value as u64) << length;
+        length += bit_length;
+        while length >= 8 {
+            lossless.push(value as u8);
+            value >>= 8;
+            length -= 8;
+        }
+    }
+    lossless.push(value as u8);
+
+    let mut header = vec![0x08, 0, 0, 0];
+    header.extend_from_slice(&[2, 0, 0, 1, 0, 0]);
+    let mut body = b"WEBP".to_vec();
+    body.extend(chunk(b"VP8X", &header));
+    body.extend(chunk(b"VP8L", &lossless));
+    body.extend(chunk(b"EXIF", tiff));
+    let mut bytes = b"RIFF".to_vec();
+    bytes.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    bytes.extend(body);
+    bytes
+}
+
+/// Renders the picture over a transparent image and returns the area it covers.
+fn render(picture: Picture) -> RgbaImage {
+    let mut image = Image::new(64, 64, [0, 0, 0, 0]);
+    image.add_picture(picture);
+    let buffer = image.render().unwrap();
+    let width = (0..64)
+        .take_while(|&x| buffer.get_pixel(x, 0).0[3] > 0)
+        .count() as u32;
+    let height = (0..64)
+        .take_while(|&y| buffer.get_pixel(0, y).0[3] > 0)
+        .count() as u32;
+    image::imageops::crop_imm(&buffer, 0, 0, width, height).to_image()
+}
+
+fn marker(picture: &RgbaImage) -> (u32, u32) {
+    let (x, y, _) = picture
+        .enumerate_pixels()
+        .find(|(_, _, pixel)| pixel.0[0] > 200 && pixel.0[2] < 50)
+        .unwrap();
+    (x, y)
+}
+
+#[test]
+fn png_orientations() {
+    for (orientation, (size, position)) in (1..=8).zip(UPRIGHT) {
+        for big_endian in [false, true] {
+            let bytes = png(&tiff(orientation, big_endian));
+            let picture = render(Picture::from_bytes(&bytes).unwrap());
+            assert_eq!(picture.dimensions(), size, "orientation {}", orientation);
+            assert_eq!(marker(&picture), position, "orientation {}", orientation);
+        }
+    }
+}
+
+#[test]
+fn jpeg_orientations() {
+    for (orientation, (size, position)) in (1..=8).zip(UPRIGHT) {
+        for big_endian in [false, true] {
+            let bytes = jpeg(&tiff(orientation, big_endian));
+            let picture = render(Picture::from_bytes(&bytes).unwrap());
+            // Each pixel of the expectations is a block of 16x16 pixels.
+            assert_eq!(picture.dimensions(), (size.0 * 16, size.1 * 16));
+            let (x, y) = marker(&picture);
+            assert_eq!((x / 16, y / 16), position, "orientation {}", orientation);
+        }
+    }
+}
+
+#[test]
+fn webp_orientations() {
+    for (orientation, (size, _)) in (1..=8).zip(UPRIGHT) {
+        let bytes = webp(&tiff(orientation, false));
+        let picture = render(Picture::from_bytes(&bytes).unwrap());
+        assert_eq!(picture.dimensions(), size, "orientation {}", orientation);
+    }
+}
+
+#[test]
+fn orientation_is_ignored_when_disabled() {
+    let bytes = png(&tiff(6, false));
+    let picture = render(Picture::from_bytes(&bytes).unwrap().auto_orient(false));
+    assert_eq!(picture.dimensions(), (3, 2));
+    assert_eq!(marker(&picture), (0, 0));
+}
+
+#[test]
+fn invalid_exif_leaves_the_picture_untouched() {
+    let valid = tiff(6, false);
+    let cases: Vec<Vec<u8>> = vec![
+        b"not a tiff at all".to_vec(),
+        valid[..4].to_vec(),
+        valid[..12].to_vec(),
+        valid[..valid.len() - 8].to_vec(),
+        // A directory offset beyond the data.
+        [&valid[..4], &[0xFF, 0xFF, 0, 0][..], &valid[8..]].concat(),
+        // An orientation out of the range of the tag.
+        tiff(9, false),
+        tiff(0, true),
+    ];
+    for data in cases {
+        for bytes in [png(&data), jpeg(&data)] {
+            let picture = render(Picture::from_bytes(&bytes).unwrap());
+            let (width, height) = picture.dimensions();
+            assert!(width > height, "{:?}", data);
+            assert_eq!(marker(&picture), (0, 0), "{:?}", data);
+        }
+    }
+}